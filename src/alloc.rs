@@ -0,0 +1,7 @@
+use std::alloc::Allocator;
+
+/// Allocator of the asynchronous function and the value slot of [`Iter`](crate::Iter) and [`AsyncIter`](crate::AsyncIter).
+pub(crate) type SendAlloc = &'static (dyn Allocator + Sync);
+
+/// Allocator of the asynchronous function and the value slot of [`LocalIter`](crate::LocalIter) and [`LocalAsyncIter`](crate::LocalAsyncIter).
+pub(crate) type LocalAlloc = &'static dyn Allocator;
@@ -0,0 +1,72 @@
+use std::{
+    async_iter::AsyncIterator,
+    future::poll_fn,
+    pin::{Pin, pin},
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+
+use crate::{AsyncIter, LocalAsyncIter};
+
+impl<'a, T: Send + 'a> AsyncIter<'a, T> {
+    /// Create an asynchronous iterator from an [`AsyncIterator`], such as an `async gen` block.
+    ///
+    /// This method is available with the `nightly` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #![feature(async_iterator, gen_blocks)]
+    /// use futures::StreamExt;
+    /// use yield_return::AsyncIter;
+    /// # futures::executor::block_on(async {
+    /// let iter = AsyncIter::from_async_iter(async gen {
+    ///     yield 1;
+    ///     yield 2;
+    /// });
+    /// let list: Vec<_> = iter.collect().await;
+    /// assert_eq!(list, vec![1, 2]);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn from_async_iter(iter: impl AsyncIterator<Item = T> + Send + 'a) -> Self {
+        Self::new(|mut cx| async move {
+            let mut iter = pin!(iter);
+            while let Some(value) = poll_fn(|cx| iter.as_mut().poll_next(cx)).await {
+                cx.ret(value).await;
+            }
+        })
+    }
+}
+
+impl<T> AsyncIterator for AsyncIter<'_, T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Stream::poll_next(self, cx)
+    }
+}
+
+impl<'a, T: 'a> LocalAsyncIter<'a, T> {
+    /// Create an asynchronous iterator from an [`AsyncIterator`], such as an `async gen` block.
+    ///
+    /// This method is available with the `nightly` feature.
+    #[track_caller]
+    pub fn from_async_iter(iter: impl AsyncIterator<Item = T> + 'a) -> Self {
+        Self::new(|mut cx| async move {
+            let mut iter = pin!(iter);
+            while let Some(value) = poll_fn(|cx| iter.as_mut().poll_next(cx)).await {
+                cx.ret(value).await;
+            }
+        })
+    }
+}
+
+impl<T> AsyncIterator for LocalAsyncIter<'_, T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Stream::poll_next(self, cx)
+    }
+}
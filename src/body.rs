@@ -0,0 +1,177 @@
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    sync::{Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures::{StreamExt, stream::FusedStream};
+use http_body::{Body, Frame, SizeHint};
+
+use crate::AsyncIter;
+
+/// [`Body`] implementation over an [`AsyncIter`] yielding frames.
+///
+/// Created by [`AsyncIter::into_body`].
+///
+/// The asynchronous function yields data frames with [`Frame::data`] and,
+/// at the end, trailers with [`Frame::trailers`].
+///
+/// # Example
+///
+/// ```
+/// use http_body::{Body, Frame};
+/// use yield_return::AsyncIter;
+/// let body = AsyncIter::new(|mut y| async move {
+///     y.ret(Frame::data(&b"Hello, "[..])).await;
+///     y.ret(Frame::data(&b"world!"[..])).await;
+/// })
+/// .into_body()
+/// .with_exact_size(13);
+/// assert_eq!(body.size_hint().exact(), Some(13));
+/// ```
+pub struct AsyncIterBody<'a, D> {
+    iter: AsyncIter<'a, Frame<D>>,
+    size_hint: SizeHint,
+}
+
+impl<'a, D: Buf> AsyncIterBody<'a, D> {
+    pub fn new(iter: AsyncIter<'a, Frame<D>>) -> Self {
+        Self {
+            iter,
+            size_hint: SizeHint::default(),
+        }
+    }
+
+    /// Sets the size hint of the data frames.
+    ///
+    /// The hint is reduced by the size of each data frame as it is yielded.
+    pub fn with_size_hint(mut self, size_hint: SizeHint) -> Self {
+        self.size_hint = size_hint;
+        self
+    }
+
+    /// Sets the exact total size of the data frames.
+    pub fn with_exact_size(self, size: u64) -> Self {
+        self.with_size_hint(SizeHint::with_exact(size))
+    }
+
+    /// Converts this body into a body that implements [`Sync`].
+    pub fn into_sync(self) -> SyncAsyncIterBody<'a, D> {
+        SyncAsyncIterBody(Mutex::new(self))
+    }
+
+    pub fn into_inner(self) -> AsyncIter<'a, Frame<D>> {
+        self.iter
+    }
+}
+
+impl<D: Buf> Body for AsyncIterBody<'_, D> {
+    type Data = D;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let frame = this.iter.poll_next_unpin(cx);
+        if let Poll::Ready(Some(frame)) = &frame
+            && let Some(data) = frame.data_ref()
+        {
+            let len = data.remaining() as u64;
+            let mut size_hint = SizeHint::new();
+            size_hint.set_lower(this.size_hint.lower().saturating_sub(len));
+            if let Some(upper) = this.size_hint.upper() {
+                size_hint.set_upper(upper.saturating_sub(len));
+            }
+            this.size_hint = size_hint;
+        }
+        frame.map(|frame| frame.map(Ok))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.iter.is_terminated()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.iter.is_terminated() {
+            SizeHint::with_exact(0)
+        } else {
+            self.size_hint
+        }
+    }
+}
+
+/// [`Body`] implementation over an [`AsyncIter`] yielding frames that also implements [`Sync`].
+///
+/// Created by [`AsyncIter::into_sync_body`] or [`AsyncIterBody::into_sync`].
+///
+/// `poll_frame` accesses the body without locking.
+/// `is_end_stream` and `size_hint` take a lock that is never contended.
+pub struct SyncAsyncIterBody<'a, D>(Mutex<AsyncIterBody<'a, D>>);
+
+impl<'a, D: Buf> SyncAsyncIterBody<'a, D> {
+    pub fn new(iter: AsyncIter<'a, Frame<D>>) -> Self {
+        AsyncIterBody::new(iter).into_sync()
+    }
+
+    /// Sets the size hint of the data frames.
+    ///
+    /// The hint is reduced by the size of each data frame as it is yielded.
+    pub fn with_size_hint(self, size_hint: SizeHint) -> Self {
+        self.into_unsync().with_size_hint(size_hint).into_sync()
+    }
+
+    /// Sets the exact total size of the data frames.
+    pub fn with_exact_size(self, size: u64) -> Self {
+        self.with_size_hint(SizeHint::with_exact(size))
+    }
+
+    /// Converts this body back into an [`AsyncIterBody`].
+    pub fn into_unsync(self) -> AsyncIterBody<'a, D> {
+        self.0.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, AsyncIterBody<'a, D>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<D: Buf> Body for SyncAsyncIterBody<'_, D> {
+    type Data = D;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let body = self
+            .get_mut()
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        Pin::new(body).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.lock().is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.lock().size_hint()
+    }
+}
+
+impl<'a, D: Buf> AsyncIter<'a, Frame<D>> {
+    /// Converts this iterator into a [`Body`].
+    pub fn into_body(self) -> AsyncIterBody<'a, D> {
+        AsyncIterBody::new(self)
+    }
+
+    /// Converts this iterator into a [`Body`] that implements [`Sync`].
+    pub fn into_sync_body(self) -> SyncAsyncIterBody<'a, D> {
+        SyncAsyncIterBody::new(self)
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Limits the number of consecutive items a stream returns without yielding to the executor.
+#[derive(Default)]
+pub(crate) struct Budget {
+    limit: Option<usize>,
+    used: usize,
+}
+
+impl Budget {
+    #[track_caller]
+    pub fn new(limit: usize) -> Self {
+        assert!(limit > 0, "The budget must be greater than 0.");
+        Self {
+            limit: Some(limit),
+            used: 0,
+        }
+    }
+
+    /// Returns `true` if the next call to [`poll_next`](Self::poll_next) will return `Poll::Pending`.
+    pub fn is_exhausted(&self) -> bool {
+        self.limit.is_some_and(|limit| self.used >= limit)
+    }
+
+    pub fn poll_next<T>(
+        &mut self,
+        cx: &mut Context,
+        f: impl FnOnce(&mut Context) -> Poll<T>,
+    ) -> Poll<T> {
+        if self.is_exhausted() {
+            self.used = 0;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let poll = f(cx);
+        if poll.is_ready() {
+            self.used += 1;
+        } else {
+            self.used = 0;
+        }
+        poll
+    }
+}
+
+/// Future returned by `yield_now`.
+pub(crate) struct YieldNow(bool);
+
+impl YieldNow {
+    pub fn new() -> Self {
+        Self(false)
+    }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    ops::{Coroutine, CoroutineState},
+    pin::{Pin, pin},
+};
+
+use crate::{Iter, LocalIter};
+
+impl<'a, T: 'a + Send> Iter<'a, T> {
+    /// Create an iterator from a coroutine.
+    ///
+    /// The coroutine is resumed each time a value is requested.
+    /// `gen` blocks implement [`Iterator`] and can be passed to [`from_iter`](Self::from_iter) instead.
+    ///
+    /// This method is available with the `nightly` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #![feature(coroutines, stmt_expr_attributes)]
+    /// use yield_return::Iter;
+    /// let iter = Iter::from_coroutine(#[coroutine] || {
+    ///     yield 1;
+    ///     yield 2;
+    /// });
+    /// let list: Vec<_> = iter.collect();
+    /// assert_eq!(list, vec![1, 2]);
+    /// ```
+    #[track_caller]
+    pub fn from_coroutine(co: impl Coroutine<Yield = T, Return = ()> + Send + 'a) -> Self {
+        Self::new(|mut cx| async move {
+            let mut co = pin!(co);
+            while let CoroutineState::Yielded(value) = co.as_mut().resume(()) {
+                cx.ret(value).await;
+            }
+        })
+    }
+}
+
+impl<T> Coroutine for Iter<'_, T> {
+    type Yield = T;
+    type Return = ();
+
+    /// Resumes the asynchronous function until it yields a value or completes.
+    ///
+    /// Resuming after completion returns [`CoroutineState::Complete`] again.
+    fn resume(self: Pin<&mut Self>, _arg: ()) -> CoroutineState<T, ()> {
+        match self.get_mut().next() {
+            Some(value) => CoroutineState::Yielded(value),
+            None => CoroutineState::Complete(()),
+        }
+    }
+}
+
+impl<'a, T: 'a> LocalIter<'a, T> {
+    /// Create an iterator from a coroutine.
+    ///
+    /// The coroutine is resumed each time a value is requested.
+    /// `gen` blocks implement [`Iterator`] and can be passed to [`from_iter`](Self::from_iter) instead.
+    ///
+    /// This method is available with the `nightly` feature.
+    #[track_caller]
+    pub fn from_coroutine(co: impl Coroutine<Yield = T, Return = ()> + 'a) -> Self {
+        Self::new(|mut cx| async move {
+            let mut co = pin!(co);
+            while let CoroutineState::Yielded(value) = co.as_mut().resume(()) {
+                cx.ret(value).await;
+            }
+        })
+    }
+}
+
+impl<T> Coroutine for LocalIter<'_, T> {
+    type Yield = T;
+    type Return = ();
+
+    /// Resumes the asynchronous function until it yields a value or completes.
+    ///
+    /// Resuming after completion returns [`CoroutineState::Complete`] again.
+    fn resume(self: Pin<&mut Self>, _arg: ()) -> CoroutineState<T, ()> {
+        match self.get_mut().next() {
+            Some(value) => CoroutineState::Yielded(value),
+            None => CoroutineState::Complete(()),
+        }
+    }
+}
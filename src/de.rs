@@ -0,0 +1,155 @@
+use std::{
+    fmt,
+    panic::resume_unwind,
+    sync::mpsc::{SyncSender, sync_channel},
+    thread,
+};
+
+use serde::{
+    Deserializer,
+    de::{DeserializeOwned, DeserializeSeed, Error, SeqAccess, Visitor},
+};
+
+use crate::{Iter, LocalIter};
+
+/// [`DeserializeSeed`] that passes each element of a sequence to the iterator created by
+/// [`Iter::deserialize_seq_with`] or [`LocalIter::deserialize_seq_with`].
+pub struct SeqSeed<T> {
+    send: Box<dyn FnMut(T) -> bool + Send>,
+}
+
+impl<'de, T: DeserializeOwned> DeserializeSeed<'de> for SeqSeed<T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for SeqSeed<T> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(value) = seq.next_element()? {
+            if !(self.send)(value) {
+                return Err(A::Error::custom("the iterator was dropped"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn run_seq<T, E>(tx: SyncSender<Result<T, E>>, f: impl FnOnce(SeqSeed<T>) -> Result<(), E>)
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    let seed_tx = tx.clone();
+    let seed = SeqSeed {
+        send: Box::new(move |value| seed_tx.send(Ok(value)).is_ok()),
+    };
+    if let Err(e) = f(seed) {
+        let _ = tx.send(Err(e));
+    }
+}
+
+impl<T: DeserializeOwned + Send + 'static> Iter<'static, T> {
+    /// Create an iterator that yields each element of the top-level sequence of `deserializer` as it is parsed.
+    ///
+    /// The deserializer runs on a separate thread that is started when the first value is requested,
+    /// and parses at most one element ahead of the consumer.
+    /// If deserialization fails, the error is yielded as the last value.
+    /// If the iterator is dropped, deserialization is aborted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yield_return::Iter;
+    /// let value = serde_json::json!([1, 2, 3]);
+    /// let list: Vec<i32> = Iter::deserialize_seq(value)
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(list, vec![1, 2, 3]);
+    /// ```
+    #[track_caller]
+    pub fn deserialize_seq<D>(deserializer: D) -> Iter<'static, Result<T, D::Error>>
+    where
+        D: Deserializer<'static> + Send + 'static,
+        D::Error: Send + 'static,
+    {
+        Iter::deserialize_seq_with(|seed| seed.deserialize(deserializer))
+    }
+
+    /// Create an iterator that yields each element of a sequence deserialized by `f` as it is parsed.
+    ///
+    /// `f` runs on a separate thread and deserializes the sequence by passing the given [`SeqSeed`]
+    /// to [`DeserializeSeed::deserialize`].
+    /// This allows the deserializer to be created on that thread, and to be used through a mutable reference.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::de::DeserializeSeed;
+    /// use yield_return::Iter;
+    /// let json = b"[1, 2, 3]";
+    /// let iter = Iter::<i32>::deserialize_seq_with(move |seed| {
+    ///     let mut de = serde_json::Deserializer::from_reader(&json[..]);
+    ///     seed.deserialize(&mut de)?;
+    ///     de.end()
+    /// });
+    /// let list: Vec<_> = iter.collect::<Result<_, _>>().unwrap();
+    /// assert_eq!(list, vec![1, 2, 3]);
+    /// ```
+    #[track_caller]
+    pub fn deserialize_seq_with<E: Send + 'static>(
+        f: impl FnOnce(SeqSeed<T>) -> Result<(), E> + Send + 'static,
+    ) -> Iter<'static, Result<T, E>> {
+        Iter::new(|mut y| async move {
+            let (tx, rx) = sync_channel(0);
+            let handle = thread::spawn(move || run_seq(tx, f));
+            while let Ok(value) = rx.recv() {
+                y.ret(value).await;
+            }
+            if let Err(e) = handle.join() {
+                resume_unwind(e);
+            }
+        })
+    }
+}
+
+impl<T: DeserializeOwned + Send + 'static> LocalIter<'static, T> {
+    /// Create an iterator that yields each element of the top-level sequence of `deserializer` as it is parsed.
+    ///
+    /// See [`Iter::deserialize_seq`] for details.
+    #[track_caller]
+    pub fn deserialize_seq<D>(deserializer: D) -> LocalIter<'static, Result<T, D::Error>>
+    where
+        D: Deserializer<'static> + Send + 'static,
+        D::Error: Send + 'static,
+    {
+        LocalIter::deserialize_seq_with(|seed| seed.deserialize(deserializer))
+    }
+
+    /// Create an iterator that yields each element of a sequence deserialized by `f` as it is parsed.
+    ///
+    /// See [`Iter::deserialize_seq_with`] for details.
+    #[track_caller]
+    pub fn deserialize_seq_with<E: Send + 'static>(
+        f: impl FnOnce(SeqSeed<T>) -> Result<(), E> + Send + 'static,
+    ) -> LocalIter<'static, Result<T, E>> {
+        LocalIter::new(|mut y| async move {
+            let (tx, rx) = sync_channel(0);
+            let handle = thread::spawn(move || run_seq(tx, f));
+            while let Ok(value) = rx.recv() {
+                y.ret(value).await;
+            }
+            if let Err(e) = handle.join() {
+                resume_unwind(e);
+            }
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+#[cfg(feature = "diagnostics")]
+use std::{borrow::Cow, panic::Location};
+
+/// Information about an iterator included in panic messages and `Debug` output.
+///
+/// Without the `diagnostics` feature, this type is empty and records nothing.
+#[cfg(feature = "diagnostics")]
+pub(crate) struct Diagnostics {
+    location: &'static Location<'static>,
+    name: Option<Cow<'static, str>>,
+    last_ret: Option<&'static Location<'static>>,
+}
+
+#[cfg(feature = "diagnostics")]
+impl Diagnostics {
+    #[track_caller]
+    pub fn new() -> Self {
+        Self {
+            location: Location::caller(),
+            name: None,
+            last_ret: None,
+        }
+    }
+
+    #[track_caller]
+    pub fn record_ret(&mut self) {
+        self.last_ret = Some(Location::caller());
+    }
+
+    pub fn set_name(&mut self, name: Cow<'static, str>) {
+        self.name = Some(name);
+    }
+
+    pub fn debug_fields(&self, d: &mut fmt::DebugStruct) {
+        d.field("name", &self.name)
+            .field("location", &format_args!("{}", self.location))
+            .field("last_ret", &self.last_ret.map(|l| l.to_string()));
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " (iterator")?;
+        if let Some(name) = &self.name {
+            write!(f, " `{name}`")?;
+        }
+        write!(f, " created at {}", self.location)?;
+        if let Some(last_ret) = self.last_ret {
+            write!(f, ", last `ret` at {last_ret}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(not(feature = "diagnostics"))]
+pub(crate) struct Diagnostics;
+
+#[cfg(not(feature = "diagnostics"))]
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn record_ret(&mut self) {}
+}
+
+#[cfg(not(feature = "diagnostics"))]
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    io::{self, BufRead, Read, Write},
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use futures::{AsyncBufRead, AsyncRead, AsyncWrite, Stream, StreamExt};
+
+use crate::{
+    AsyncIter, AsyncIterContext, Iter, IterContext, LocalAsyncIter, LocalAsyncIterContext,
+    LocalIter, LocalIterContext,
+};
+
+/// [`Read`] and [`BufRead`] implementation over an iterator of byte chunks.
+///
+/// Created by [`Iter::into_reader`] and [`LocalIter::into_reader`],
+/// or by [`IterReader::new`] from any iterator.
+///
+/// Bytes are copied directly from each chunk into the caller's buffer.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+/// use yield_return::Iter;
+/// let iter = Iter::new(|mut y| async move {
+///     y.ret(b"Hello, ".to_vec()).await;
+///     y.ret(b"world!".to_vec()).await;
+/// });
+/// let mut s = String::new();
+/// iter.into_reader().read_to_string(&mut s).unwrap();
+/// assert_eq!(s, "Hello, world!");
+/// ```
+pub struct IterReader<I: Iterator> {
+    iter: I,
+    chunk: Option<I::Item>,
+    pos: usize,
+}
+
+impl<I> IterReader<I>
+where
+    I: Iterator<Item: AsRef<[u8]>>,
+{
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            iter: iter.into_iter(),
+            chunk: None,
+            pos: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    fn remaining(&mut self) -> &[u8] {
+        while self
+            .chunk
+            .as_ref()
+            .is_none_or(|chunk| self.pos >= chunk.as_ref().len())
+        {
+            self.pos = 0;
+            self.chunk = self.iter.next();
+            if self.chunk.is_none() {
+                return &[];
+            }
+        }
+        &self.chunk.as_ref().unwrap().as_ref()[self.pos..]
+    }
+}
+
+impl<I> Read for IterReader<I>
+where
+    I: Iterator<Item: AsRef<[u8]>>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.remaining().read(buf)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<I> BufRead for IterReader<I>
+where
+    I: Iterator<Item: AsRef<[u8]>>,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+impl<T: AsRef<[u8]>> Iter<'_, T> {
+    /// Converts this iterator of byte chunks into a [`Read`] and [`BufRead`] implementation.
+    pub fn into_reader(self) -> IterReader<Self> {
+        IterReader::new(self)
+    }
+}
+
+impl<T: AsRef<[u8]>> LocalIter<'_, T> {
+    /// Converts this iterator of byte chunks into a [`Read`] and [`BufRead`] implementation.
+    pub fn into_reader(self) -> IterReader<Self> {
+        IterReader::new(self)
+    }
+}
+
+/// [`Write`] implementation that yields the written bytes as chunks from an [`Iter`].
+///
+/// Created by [`IterContext::writer`].
+///
+/// Written bytes are buffered until [`ret_buffered`](Self::ret_buffered) or [`finish`](Self::finish) is awaited.
+/// Bytes that have not been yielded when the writer is dropped are discarded.
+#[must_use = "call `finish` to yield the written bytes"]
+pub struct IterWriter<'a, 'b, T> {
+    cx: &'a mut IterContext<'b, T>,
+    buf: Vec<u8>,
+}
+
+impl<T: From<Vec<u8>> + Send> IterWriter<'_, '_, T> {
+    /// Yields the buffered bytes as a single chunk. Does nothing if the buffer is empty.
+    pub async fn ret_buffered(&mut self) {
+        if !self.buf.is_empty() {
+            self.cx.ret(T::from(std::mem::take(&mut self.buf))).await;
+        }
+    }
+
+    /// Yields the remaining buffered bytes and consumes the writer.
+    pub async fn finish(mut self) {
+        self.ret_buffered().await;
+    }
+}
+
+impl<T> Write for IterWriter<'_, '_, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, T: From<Vec<u8>> + Send> IterContext<'a, T> {
+    /// Returns a [`Write`] implementation that yields the written bytes as chunks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::{Read, Write};
+    /// use yield_return::Iter;
+    /// let iter = Iter::<Vec<u8>>::new(|mut y| async move {
+    ///     let mut w = y.writer();
+    ///     write!(w, "{}, {}", 1, 2).unwrap();
+    ///     w.finish().await;
+    /// });
+    /// let mut s = String::new();
+    /// iter.into_reader().read_to_string(&mut s).unwrap();
+    /// assert_eq!(s, "1, 2");
+    /// ```
+    pub fn writer(&mut self) -> IterWriter<'_, 'a, T> {
+        IterWriter {
+            cx: self,
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// [`Write`] implementation that yields the written bytes as chunks from a [`LocalIter`].
+///
+/// Created by [`LocalIterContext::writer`].
+///
+/// Written bytes are buffered until [`ret_buffered`](Self::ret_buffered) or [`finish`](Self::finish) is awaited.
+/// Bytes that have not been yielded when the writer is dropped are discarded.
+#[must_use = "call `finish` to yield the written bytes"]
+pub struct LocalIterWriter<'a, 'b, T> {
+    cx: &'a mut LocalIterContext<'b, T>,
+    buf: Vec<u8>,
+}
+
+impl<T: From<Vec<u8>>> LocalIterWriter<'_, '_, T> {
+    /// Yields the buffered bytes as a single chunk. Does nothing if the buffer is empty.
+    pub async fn ret_buffered(&mut self) {
+        if !self.buf.is_empty() {
+            self.cx.ret(T::from(std::mem::take(&mut self.buf))).await;
+        }
+    }
+
+    /// Yields the remaining buffered bytes and consumes the writer.
+    pub async fn finish(mut self) {
+        self.ret_buffered().await;
+    }
+}
+
+impl<T> Write for LocalIterWriter<'_, '_, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, T: From<Vec<u8>>> LocalIterContext<'a, T> {
+    /// Returns a [`Write`] implementation that yields the written bytes as chunks.
+    pub fn writer(&mut self) -> LocalIterWriter<'_, 'a, T> {
+        LocalIterWriter {
+            cx: self,
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// [`AsyncRead`] and [`AsyncBufRead`] implementation over a stream of byte chunks.
+///
+/// Created by [`AsyncIter::into_reader`] and [`LocalAsyncIter::into_reader`],
+/// or by [`AsyncIterReader::new`] from any stream.
+///
+/// Bytes are copied directly from each chunk into the caller's buffer.
+///
+/// # Example
+///
+/// ```
+/// use futures::AsyncReadExt;
+/// use yield_return::AsyncIter;
+/// # futures::executor::block_on(async {
+/// let iter = AsyncIter::new(|mut y| async move {
+///     y.ret(b"Hello, ".to_vec()).await;
+///     y.ret(b"world!".to_vec()).await;
+/// });
+/// let mut s = String::new();
+/// iter.into_reader().read_to_string(&mut s).await.unwrap();
+/// assert_eq!(s, "Hello, world!");
+/// # });
+/// ```
+pub struct AsyncIterReader<S: Stream> {
+    stream: S,
+    chunk: Option<S::Item>,
+    pos: usize,
+}
+
+impl<S> AsyncIterReader<S>
+where
+    S: Stream<Item: AsRef<[u8]>> + Unpin,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            chunk: None,
+            pos: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn poll_remaining(&mut self, cx: &mut Context) -> Poll<&[u8]> {
+        while self
+            .chunk
+            .as_ref()
+            .is_none_or(|chunk| self.pos >= chunk.as_ref().len())
+        {
+            self.pos = 0;
+            self.chunk = ready!(self.stream.poll_next_unpin(cx));
+            if self.chunk.is_none() {
+                return Poll::Ready(&[]);
+            }
+        }
+        Poll::Ready(&self.chunk.as_ref().unwrap().as_ref()[self.pos..])
+    }
+}
+
+impl<S: Stream + Unpin> Unpin for AsyncIterReader<S> {}
+
+impl<S> AsyncRead for AsyncIterReader<S>
+where
+    S: Stream<Item: AsRef<[u8]>> + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = ready!(this.poll_remaining(cx)).read(buf)?;
+        this.pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<S> AsyncBufRead for AsyncIterReader<S>
+where
+    S: Stream<Item: AsRef<[u8]>> + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.get_mut().poll_remaining(cx).map(Ok)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().pos += amt;
+    }
+}
+
+impl<T: AsRef<[u8]>> AsyncIter<'_, T> {
+    /// Converts this stream of byte chunks into an [`AsyncRead`] and [`AsyncBufRead`] implementation.
+    pub fn into_reader(self) -> AsyncIterReader<Self> {
+        AsyncIterReader::new(self)
+    }
+}
+
+impl<T: AsRef<[u8]>> LocalAsyncIter<'_, T> {
+    /// Converts this stream of byte chunks into an [`AsyncRead`] and [`AsyncBufRead`] implementation.
+    pub fn into_reader(self) -> AsyncIterReader<Self> {
+        AsyncIterReader::new(self)
+    }
+}
+
+/// [`AsyncWrite`] implementation that yields each write as a chunk from an [`AsyncIter`].
+///
+/// Created by [`AsyncIterContext::async_writer`].
+///
+/// `poll_write` returns `Poll::Pending` until the consumer takes the previous chunk,
+/// and `poll_flush` and `poll_close` return `Poll::Pending` until the consumer takes the last chunk.
+/// A chunk that has not been taken when the asynchronous function returns is yielded afterwards.
+pub struct AsyncIterWriter<'a, 'b, T> {
+    cx: &'a mut IterContext<'b, T>,
+}
+
+impl<T: From<Vec<u8>>> AsyncWrite for AsyncIterWriter<'_, '_, T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        ready!(this.cx.poll_sent(cx));
+        this.cx.set_detached(T::from(buf.to_vec()));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().cx.poll_sent(cx).map(Ok)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<'a, T: From<Vec<u8>> + Send> AsyncIterContext<'a, T> {
+    /// Returns an [`AsyncWrite`] implementation that yields each write as a chunk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::{AsyncWriteExt, StreamExt};
+    /// use yield_return::AsyncIter;
+    /// # futures::executor::block_on(async {
+    /// let iter = AsyncIter::<Vec<u8>>::new(|mut y| async move {
+    ///     let mut w = y.async_writer();
+    ///     w.write_all(b"abc").await.unwrap();
+    ///     w.write_all(b"de").await.unwrap();
+    ///     w.close().await.unwrap();
+    /// });
+    /// let list: Vec<_> = iter.collect().await;
+    /// assert_eq!(list, vec![b"abc".to_vec(), b"de".to_vec()]);
+    /// # });
+    /// ```
+    pub fn async_writer(&mut self) -> AsyncIterWriter<'_, 'a, T> {
+        AsyncIterWriter { cx: self }
+    }
+}
+
+/// [`AsyncWrite`] implementation that yields each write as a chunk from a [`LocalAsyncIter`].
+///
+/// Created by [`LocalAsyncIterContext::async_writer`].
+///
+/// `poll_write` returns `Poll::Pending` until the consumer takes the previous chunk,
+/// and `poll_flush` and `poll_close` return `Poll::Pending` until the consumer takes the last chunk.
+/// A chunk that has not been taken when the asynchronous function returns is yielded afterwards.
+pub struct LocalAsyncIterWriter<'a, 'b, T> {
+    cx: &'a mut LocalIterContext<'b, T>,
+}
+
+impl<T: From<Vec<u8>>> AsyncWrite for LocalAsyncIterWriter<'_, '_, T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        ready!(this.cx.poll_sent(cx));
+        this.cx.set_detached(T::from(buf.to_vec()));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().cx.poll_sent(cx).map(Ok)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<'a, T: From<Vec<u8>>> LocalAsyncIterContext<'a, T> {
+    /// Returns an [`AsyncWrite`] implementation that yields each write as a chunk.
+    pub fn async_writer(&mut self) -> LocalAsyncIterWriter<'_, 'a, T> {
+        LocalAsyncIterWriter { cx: self }
+    }
+}
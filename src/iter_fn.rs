@@ -0,0 +1,306 @@
+use std::{fmt, future::Future, marker::PhantomData, pin::Pin};
+
+use crate::{
+    AsyncIter, AsyncIterContext, Iter, IterContext, LocalAsyncIter, LocalAsyncIterContext,
+    LocalIter, LocalIterContext,
+};
+
+/// [`IterFn`] with a boxed function, which can be named in struct fields.
+pub type BoxIterFn<'a, T> = IterFn<
+    'a,
+    T,
+    Box<
+        dyn Fn(IterContext<'a, T>) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>
+            + Send
+            + Sync
+            + 'a,
+    >,
+>;
+
+/// [`LocalIterFn`] with a boxed function, which can be named in struct fields.
+pub type BoxLocalIterFn<'a, T> = LocalIterFn<
+    'a,
+    T,
+    Box<dyn Fn(LocalIterContext<'a, T>) -> Pin<Box<dyn Future<Output = ()> + 'a>> + 'a>,
+>;
+
+/// [`AsyncIterFn`] with a boxed function, which can be named in struct fields.
+pub type BoxAsyncIterFn<'a, T> = AsyncIterFn<
+    'a,
+    T,
+    Box<
+        dyn Fn(AsyncIterContext<'a, T>) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>
+            + Send
+            + Sync
+            + 'a,
+    >,
+>;
+
+/// [`LocalAsyncIterFn`] with a boxed function, which can be named in struct fields.
+pub type BoxLocalAsyncIterFn<'a, T> = LocalAsyncIterFn<
+    'a,
+    T,
+    Box<dyn Fn(LocalAsyncIterContext<'a, T>) -> Pin<Box<dyn Future<Output = ()> + 'a>> + 'a>,
+>;
+
+/// Factory of [`Iter`] that can be iterated many times, running the asynchronous function each time.
+///
+/// Similar to C#'s `IEnumerable`.
+///
+/// # Example
+///
+/// ```
+/// use yield_return::IterFn;
+/// let iter_fn = IterFn::new(|mut y| async move {
+///     y.ret(1).await;
+///     y.ret(2).await;
+/// });
+/// assert_eq!(iter_fn.iter().collect::<Vec<_>>(), vec![1, 2]);
+/// for value in &iter_fn {
+///     println!("{value}");
+/// }
+/// ```
+pub struct IterFn<'a, T, F> {
+    f: F,
+    _phantom: PhantomData<fn(IterContext<'a, T>)>,
+}
+
+impl<'a, T, F, Fut> IterFn<'a, T, F>
+where
+    T: Send + 'a,
+    F: Fn(IterContext<'a, T>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'a,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates an iterator by running the asynchronous function.
+    #[track_caller]
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter::new(&self.f)
+    }
+
+    /// Boxes the function so that the type can be named.
+    pub fn boxed(self) -> BoxIterFn<'a, T>
+    where
+        F: Send + Sync + 'a,
+    {
+        IterFn::new(Box::new(move |cx| Box::pin((self.f)(cx))))
+    }
+}
+
+impl<'a, T, F, Fut> IntoIterator for &IterFn<'a, T, F>
+where
+    T: Send + 'a,
+    F: Fn(IterContext<'a, T>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'a,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    #[track_caller]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, F: Clone> Clone for IterFn<'_, T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F> fmt::Debug for IterFn<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterFn").finish_non_exhaustive()
+    }
+}
+
+/// Factory of [`LocalIter`] that can be iterated many times, running the asynchronous function each time.
+///
+/// Similar to C#'s `IEnumerable`.
+pub struct LocalIterFn<'a, T, F> {
+    f: F,
+    _phantom: PhantomData<fn(LocalIterContext<'a, T>)>,
+}
+
+impl<'a, T, F, Fut> LocalIterFn<'a, T, F>
+where
+    T: 'a,
+    F: Fn(LocalIterContext<'a, T>) -> Fut,
+    Fut: Future<Output = ()> + 'a,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates an iterator by running the asynchronous function.
+    #[track_caller]
+    pub fn iter(&self) -> LocalIter<'a, T> {
+        LocalIter::new(&self.f)
+    }
+
+    /// Boxes the function so that the type can be named.
+    pub fn boxed(self) -> BoxLocalIterFn<'a, T>
+    where
+        F: 'a,
+    {
+        LocalIterFn::new(Box::new(move |cx| Box::pin((self.f)(cx))))
+    }
+}
+
+impl<'a, T, F, Fut> IntoIterator for &LocalIterFn<'a, T, F>
+where
+    T: 'a,
+    F: Fn(LocalIterContext<'a, T>) -> Fut,
+    Fut: Future<Output = ()> + 'a,
+{
+    type Item = T;
+    type IntoIter = LocalIter<'a, T>;
+
+    #[track_caller]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, F: Clone> Clone for LocalIterFn<'_, T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F> fmt::Debug for LocalIterFn<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalIterFn").finish_non_exhaustive()
+    }
+}
+
+/// Factory of [`AsyncIter`] that can be iterated many times, running the asynchronous function each time.
+///
+/// # Example
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::StreamExt;
+/// use yield_return::AsyncIterFn;
+/// let iter_fn = AsyncIterFn::new(|mut y| async move {
+///     y.ret(1).await;
+///     y.ret(2).await;
+/// });
+/// assert_eq!(iter_fn.iter().collect::<Vec<_>>().await, vec![1, 2]);
+/// assert_eq!(iter_fn.iter().collect::<Vec<_>>().await, vec![1, 2]);
+/// # });
+/// ```
+pub struct AsyncIterFn<'a, T, F> {
+    f: F,
+    _phantom: PhantomData<fn(AsyncIterContext<'a, T>)>,
+}
+
+impl<'a, T, F, Fut> AsyncIterFn<'a, T, F>
+where
+    T: Send + 'a,
+    F: Fn(AsyncIterContext<'a, T>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'a,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a stream by running the asynchronous function.
+    #[track_caller]
+    pub fn iter(&self) -> AsyncIter<'a, T>
+    where
+        F: Sync,
+    {
+        AsyncIter::new(&self.f)
+    }
+
+    /// Boxes the function so that the type can be named.
+    pub fn boxed(self) -> BoxAsyncIterFn<'a, T>
+    where
+        F: Send + Sync + 'a,
+    {
+        AsyncIterFn::new(Box::new(move |cx| Box::pin((self.f)(cx))))
+    }
+}
+
+impl<T, F: Clone> Clone for AsyncIterFn<'_, T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F> fmt::Debug for AsyncIterFn<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncIterFn").finish_non_exhaustive()
+    }
+}
+
+/// Factory of [`LocalAsyncIter`] that can be iterated many times, running the asynchronous function each time.
+pub struct LocalAsyncIterFn<'a, T, F> {
+    f: F,
+    _phantom: PhantomData<fn(LocalAsyncIterContext<'a, T>)>,
+}
+
+impl<'a, T, F, Fut> LocalAsyncIterFn<'a, T, F>
+where
+    T: 'a,
+    F: Fn(LocalAsyncIterContext<'a, T>) -> Fut,
+    Fut: Future<Output = ()> + 'a,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a stream by running the asynchronous function.
+    #[track_caller]
+    pub fn iter(&self) -> LocalAsyncIter<'a, T> {
+        LocalAsyncIter::new(&self.f)
+    }
+
+    /// Boxes the function so that the type can be named.
+    pub fn boxed(self) -> BoxLocalAsyncIterFn<'a, T>
+    where
+        F: 'a,
+    {
+        LocalAsyncIterFn::new(Box::new(move |cx| Box::pin((self.f)(cx))))
+    }
+}
+
+impl<T, F: Clone> Clone for LocalAsyncIterFn<'_, T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F> fmt::Debug for LocalAsyncIterFn<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalAsyncIterFn").finish_non_exhaustive()
+    }
+}
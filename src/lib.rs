@@ -26,19 +26,97 @@
 //! | [`Iterator`] | [`Iter`]      | [`LocalIter`]      |
 //! | [`Stream`]   | [`AsyncIter`] | [`LocalAsyncIter`] |
 //!
+//! [`IterFn`], [`LocalIterFn`], [`AsyncIterFn`] and [`LocalAsyncIterFn`] hold the asynchronous function
+//! and create a new iterator each time they are iterated.
+//! [`Memo`] and [`LocalMemo`] cache the values of an iterator so that they can be iterated many times
+//! without running the asynchronous function again.
+//!
 //! [`Stream`]: futures::stream::Stream
+//!
+//! ## Cargo features
+//!
+//! - `allocator-api`: Requires a nightly compiler. Adds `new_in` to each iterator type,
+//!   which allocates the asynchronous function with a custom `Allocator`.
+//! - `diagnostics`: Records where each iterator was created, where `ret` was last called, and an optional name set with `with_name`,
+//!   and includes them in panic messages and `Debug` output.
+//! - `http-body`: Adds `AsyncIterBody` and `SyncAsyncIterBody`, which implement [`http_body::Body`](https://docs.rs/http-body)
+//!   for an [`AsyncIter`] yielding frames.
+//! - `nightly`: Requires a nightly compiler. Adds `from_coroutine` to [`Iter`] and [`LocalIter`],
+//!   and implements `Coroutine` for them.
+//!   Adds `from_async_iter` to [`AsyncIter`] and [`LocalAsyncIter`], and implements `AsyncIterator` for them.
+//!   Overrides `Iterator::try_fold` and `Iterator::advance_by` for [`Iter`] and [`LocalIter`].
+//! - `serde`: Adds `IterSeq` and `IterMap`, which implement [`serde::Serialize`](https://docs.rs/serde)
+//!   by consuming an iterator as a sequence or a map, and `deserialize_seq`, which lazily deserializes a sequence into an iterator.
+//! - `stats`: Adds `stats` to each iterator type, returning `Stats` such as the number of yielded values
+//!   and the time spent in the asynchronous function.
+//! - `testing`: Enables the `testing` module with utilities for testing iterator bodies.
+//! - `tracing`: Opens a [`tracing`](https://docs.rs/tracing) span for each iterator, enters it whenever the asynchronous function is polled,
+//!   and emits events when a value is yielded, the iterator completes, is dropped before completion, or panics.
+
+#![cfg_attr(
+    feature = "nightly",
+    feature(async_iterator, coroutine_trait, iter_advance_by, try_trait_v2)
+)]
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
+
+#[cfg(feature = "allocator-api")]
+mod alloc;
+#[cfg(feature = "http-body")]
+mod body;
 
+#[cfg(feature = "nightly")]
+mod async_iterator;
+mod budget;
+#[cfg(feature = "nightly")]
+mod coroutine;
+#[cfg(feature = "serde")]
+mod de;
+mod diagnostics;
+mod io;
 mod iter;
+mod iter_fn;
 mod local_iter;
+mod memo;
+mod panic;
+mod pipe;
+mod pipeline;
+#[cfg(feature = "serde")]
+mod ser;
+mod slot;
+mod state;
+mod stats;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod trace;
 
 #[cfg(doctest)]
 mod tests_readme;
 
+#[cfg(feature = "http-body")]
+pub use body::{AsyncIterBody, SyncAsyncIterBody};
+#[cfg(feature = "serde")]
+pub use de::SeqSeed;
+pub use io::{
+    AsyncIterReader, AsyncIterWriter, IterReader, IterWriter, LocalAsyncIterWriter, LocalIterWriter,
+};
 pub use iter::{AsyncIter, AsyncIterContext, Iter, IterContext};
+pub use iter_fn::{
+    AsyncIterFn, BoxAsyncIterFn, BoxIterFn, BoxLocalAsyncIterFn, BoxLocalIterFn, IterFn,
+    LocalAsyncIterFn, LocalIterFn,
+};
 pub use local_iter::{LocalAsyncIter, LocalAsyncIterContext, LocalIter, LocalIterContext};
+pub use memo::{LocalMemo, LocalMemoIter, Memo, MemoIter};
+pub use panic::PanicPayload;
+pub use pipe::{Pipe, PipeInput};
+pub use pipeline::{Pipeline, PipelineIter};
+#[cfg(feature = "serde")]
+pub use ser::{IterMap, IterSeq};
+pub use state::IterState;
+#[cfg(feature = "stats")]
+pub use stats::Stats;
 
 #[deprecated(since = "0.2.0", note = "Use `LocalIter` instead.")]
 pub type Yield<'a, T> = LocalIter<'a, T>;
 
 #[deprecated(since = "0.2.0", note = "Use `LocalIterContext` instead.")]
-pub type YieldContext<T> = LocalIterContext<T>;
+pub type YieldContext<'a, T> = LocalIterContext<'a, T>;
@@ -1,30 +1,120 @@
 use std::{
+    any::Any,
+    array,
     cell::RefCell,
-    future::Future,
+    fmt,
+    future::{Future, poll_fn},
     iter::FusedIterator,
+    mem::MaybeUninit,
     ops::{Deref, DerefMut},
+    panic::{AssertUnwindSafe, Location, catch_unwind},
     pin::{Pin, pin},
     rc::Rc,
     task::{Context, Poll, Waker},
 };
 
-use futures::{Stream, StreamExt, stream::FusedStream};
+use futures::{
+    FutureExt, SinkExt, Stream, StreamExt,
+    channel::mpsc,
+    stream::{self, FusedStream},
+    task::{LocalSpawn, LocalSpawnExt, SpawnError},
+};
+
+#[cfg(feature = "allocator-api")]
+use std::alloc::{Allocator, Global};
+#[cfg(feature = "nightly")]
+use std::{num::NonZero, ops::Try};
+
+#[cfg(any(feature = "diagnostics", feature = "tracing"))]
+use std::borrow::Cow;
+
+#[cfg(feature = "stats")]
+use crate::Stats;
+#[cfg(feature = "allocator-api")]
+use crate::alloc::LocalAlloc;
+use crate::{
+    IterState, PanicPayload,
+    budget::{Budget, YieldNow},
+    slot::{Lazy, RET_NOT_AWAITED, Slot},
+    stats::StatsRecorder,
+    trace::Trace,
+};
 
-struct Sender<T>(Rc<RefCell<Option<T>>>);
+/// An iterator, stream or nested asynchronous function handed over to the consumer by the context.
+enum Delegate<'a, T> {
+    Stream(Pin<Box<dyn Stream<Item = T> + 'a>>),
+    Body(Pin<Box<dyn Future<Output = ()> + 'a>>),
+}
+
+#[cfg(not(feature = "allocator-api"))]
+type SlotPtr<'a, T> = Rc<RefCell<Slot<T, Delegate<'a, T>>>>;
+#[cfg(feature = "allocator-api")]
+type SlotPtr<'a, T> = Rc<RefCell<Slot<T, Delegate<'a, T>>>, LocalAlloc>;
+
+#[cfg(not(feature = "allocator-api"))]
+type BoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+#[cfg(feature = "allocator-api")]
+type BoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a, LocalAlloc>>;
 
-impl<T> Sender<T> {
+struct Sender<'a, T>(SlotPtr<'a, T>);
+
+impl<'a, T> Sender<'a, T> {
     #[track_caller]
     fn set(&self, value: T) {
-        let mut data = self.0.borrow_mut();
-        assert!(data.is_none(), "The result of `ret` is not await.");
-        *data = Some(value);
+        self.0.borrow_mut().set(value);
+    }
+
+    #[track_caller]
+    fn set_detached(&self, value: T) {
+        self.0.borrow_mut().set_detached(value);
+    }
+
+    #[track_caller]
+    fn set_delegate(&self, delegate: Delegate<'a, T>) {
+        self.0.borrow_mut().set_delegate(delegate);
+    }
+
+    #[track_caller]
+    fn set_lazy(&self) {
+        self.0.borrow_mut().set_lazy();
+    }
+
+    fn poll_lazy(&self, f: &mut Option<impl FnOnce() -> T>) -> Poll<()> {
+        let lazy = self.0.borrow_mut().lazy;
+        match lazy {
+            Lazy::None => {}
+            Lazy::Pending => return Poll::Pending,
+            Lazy::Take => {
+                self.0.borrow_mut().lazy = Lazy::None;
+                if let Some(f) = f.take() {
+                    let value = f();
+                    self.0.borrow_mut().push(value);
+                }
+            }
+            Lazy::Skip => {
+                self.0.borrow_mut().lazy = Lazy::None;
+                *f = None;
+            }
+        }
+        if self.0.borrow().is_blocked() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let mut d = f.debug_struct(name);
+        #[cfg(feature = "diagnostics")]
+        self.0.borrow().diagnostics.debug_fields(&mut d);
+        d.finish_non_exhaustive()
     }
 }
 
-impl<T> Future for Sender<T> {
+impl<T> Future for Sender<'_, T> {
     type Output = ();
     fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
-        if self.0.borrow().is_some() {
+        if self.0.borrow().is_blocked() {
             Poll::Pending
         } else {
             Poll::Ready(())
@@ -32,12 +122,30 @@ impl<T> Future for Sender<T> {
     }
 }
 
+struct RetWith<'s, 'a, T, F> {
+    sender: &'s mut Sender<'a, T>,
+    f: Option<F>,
+}
+
+// `f` is never pinned.
+impl<T, F> Unpin for RetWith<'_, '_, T, F> {}
+
+impl<T, F: FnOnce() -> T> Future for RetWith<'_, '_, T, F> {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.sender.poll_lazy(&mut this.f)
+    }
+}
+
 /// Non-`Send` iterator context.
 ///
 /// This type does not implement `Send`.
-pub struct LocalIterContext<T>(Sender<T>);
+///
+/// `'a` is the lifetime of the iterator, which bounds the data that nested functions can borrow.
+pub struct LocalIterContext<'a, T>(Sender<'a, T>);
 
-impl<T> LocalIterContext<T> {
+impl<'a, T> LocalIterContext<'a, T> {
     /// Yields a single value. Similar to C#'s `yield return` or Python's `yield`.
     #[track_caller]
     pub fn ret(&mut self, value: T) -> impl Future<Output = ()> {
@@ -45,40 +153,332 @@ impl<T> LocalIterContext<T> {
         &mut self.0
     }
 
+    /// Yields a value computed by `f` only if the consumer takes it.
+    ///
+    /// If the consumer skips the value with [`Iterator::nth`] or [`Iterator::skip`],
+    /// `f` is dropped without being called.
+    /// Taking the value resumes the asynchronous function one more time than [`ret`](Self::ret).
+    #[track_caller]
+    pub fn ret_with(&mut self, f: impl FnOnce() -> T) -> impl Future<Output = ()> {
+        self.0.set_lazy();
+        RetWith {
+            sender: &mut self.0,
+            f: Some(f),
+        }
+    }
+
     /// Yields all values from an iterator. Similar to Python's `yield from` or JavaScript's `yield*`.
+    ///
+    /// The asynchronous function is suspended for each value.
+    /// Use [`delegate_iter`](Self::delegate_iter) to hand a `'static` iterator over to the consumer instead.
     pub async fn ret_iter(&mut self, iter: impl IntoIterator<Item = T>) {
         for value in iter {
             self.ret(value).await;
         }
     }
+
+    /// Yields all values from an iterator without resuming the asynchronous function for each value.
+    ///
+    /// `iter` is handed over to the consumer, which pulls values from it directly until it is exhausted.
+    /// The asynchronous function is resumed only after that,
+    /// and the `size_hint` of `iter` is included in the `size_hint` of the consumer.
+    ///
+    /// Unlike [`ret_iter`](Self::ret_iter), `iter` must be `'static`.
+    #[track_caller]
+    pub fn delegate_iter(
+        &mut self,
+        iter: impl IntoIterator<Item = T, IntoIter: 'static>,
+    ) -> impl Future<Output = ()> {
+        self.0
+            .set_delegate(Delegate::Stream(Box::pin(stream::iter(iter))));
+        &mut self.0
+    }
+
+    /// Yields all values from a nested asynchronous function, such as a recursive call.
+    ///
+    /// `f` is called with a context that yields to the same iterator,
+    /// and the returned future is handed over to the consumer, which polls it directly until it completes.
+    /// Because enclosing functions are not polled while a nested function is running,
+    /// the cost per value does not depend on the nesting depth.
+    ///
+    /// The future returned by `f` can borrow data that outlives the iterator, such as the tree it walks,
+    /// but not the local variables of the enclosing function.
+    /// When the iterator is dropped, nested functions are dropped from the innermost to the outermost,
+    /// and then the enclosing function is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yield_return::{LocalIter, LocalIterContext};
+    ///
+    /// struct Node {
+    ///     value: u32,
+    ///     children: Vec<Node>,
+    /// }
+    ///
+    /// async fn walk<'a>(mut y: LocalIterContext<'a, u32>, node: &'a Node) {
+    ///     y.ret(node.value).await;
+    ///     for child in &node.children {
+    ///         y.ret_nested(|y| walk(y, child)).await;
+    ///     }
+    /// }
+    /// let leaf = |value| Node { value, children: Vec::new() };
+    /// let tree = Node { value: 1, children: vec![leaf(2), leaf(3)] };
+    /// let iter = LocalIter::new(|y| walk(y, &tree));
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    ///
+    /// The nested function cannot borrow a local variable of the enclosing function:
+    ///
+    /// ```compile_fail
+    /// use yield_return::LocalIter;
+    /// let iter = LocalIter::new(|mut y| async move {
+    ///     let values = vec![1, 2];
+    ///     let values = &values;
+    ///     y.ret_nested(move |mut y| async move {
+    ///         y.ret(values[0]).await;
+    ///     })
+    ///     .await;
+    /// });
+    /// ```
+    #[track_caller]
+    pub fn ret_nested<Fut: Future<Output = ()> + 'a>(
+        &mut self,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+    ) -> impl Future<Output = ()> {
+        let fut = f(LocalIterContext(Sender(self.0.0.clone())));
+        self.0.set_delegate(Delegate::Body(Box::pin(fut)));
+        &mut self.0
+    }
+}
+
+impl<'a, T> LocalIterContext<'a, T> {
+    #[track_caller]
+    pub(crate) fn set_detached(&mut self, value: T) {
+        self.0.set_detached(value);
+    }
+
+    pub(crate) fn poll_sent(&mut self, cx: &mut Context) -> Poll<()> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+impl<T> fmt::Debug for LocalIterContext<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_debug(f, "LocalIterContext")
+    }
 }
 
 struct Data<'a, T> {
-    value: Rc<RefCell<Option<T>>>,
-    fut: Option<Pin<Box<dyn Future<Output = ()> + 'a>>>,
+    slot: SlotPtr<'a, T>,
+    fut: Option<BoxFuture<'a>>,
+    delegates: Vec<Delegate<'a, T>>,
+    location: &'static Location<'static>,
+    state: IterState,
+    trace: Trace,
+    stats: StatsRecorder,
 }
-impl<T> Data<'_, T> {
+impl<'a, T> Data<'a, T> {
+    #[track_caller]
     fn poll_next(&mut self, cx: &mut Context) -> Poll<Option<T>> {
-        let Some(fut) = &mut self.fut else {
-            return Poll::Ready(None);
-        };
-        let poll = fut.as_mut().poll(cx);
-        match poll {
-            Poll::Ready(_) => {
-                assert!(
-                    self.value.borrow().is_none(),
-                    "The result of `ret` is not await."
-                );
-                self.fut = None;
-                Poll::Ready(None)
+        match self.poll_try_next(cx) {
+            Poll::Ready(Ok(value)) => Poll::Ready(value),
+            Poll::Ready(Err(e)) => e.resume(),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    #[track_caller]
+    fn poll_try_next(&mut self, cx: &mut Context) -> Poll<Result<Option<T>, PanicPayload>> {
+        self.poll_item(cx, true)
+            .map(|item| item.map(Option::flatten))
+    }
+
+    /// Polls the next item.
+    ///
+    /// Returns `Some(None)` if `take` is `false` and the item is a lazy value skipped without being computed.
+    #[track_caller]
+    fn poll_item(
+        &mut self,
+        cx: &mut Context,
+        take: bool,
+    ) -> Poll<Result<Option<Option<T>>, PanicPayload>> {
+        let mut polled = false;
+        loop {
+            if let Some(Delegate::Stream(stream)) = self.delegates.last_mut() {
+                match catch_unwind(AssertUnwindSafe(|| stream.as_mut().poll_next(cx))) {
+                    Ok(Poll::Ready(Some(value))) => {
+                        self.state = IterState::Suspended;
+                        self.trace.yielded();
+                        self.stats.yielded();
+                        return Poll::Ready(Ok(Some(Some(value))));
+                    }
+                    Ok(Poll::Ready(None)) => {
+                        self.delegates.pop();
+                        if self.fut.is_none() && self.delegates.is_empty() {
+                            self.state = IterState::Finished;
+                            self.trace.completed();
+                        }
+                        polled = false;
+                        continue;
+                    }
+                    Ok(Poll::Pending) => {
+                        self.stats.pending();
+                        return Poll::Pending;
+                    }
+                    Err(payload) => return Poll::Ready(Err(self.panicked(payload))),
+                }
             }
-            Poll::Pending => {
-                if let Some(value) = self.value.borrow_mut().take() {
-                    Poll::Ready(Some(value))
-                } else {
-                    Poll::Pending
+            if let Some(value) = self.take_buffered() {
+                return Poll::Ready(Ok(Some(Some(value))));
+            }
+            if let Some(delegate) = self.slot.borrow_mut().take_delegate() {
+                self.delegates.push(delegate);
+                polled = false;
+                continue;
+            }
+            if self.slot.borrow_mut().resolve_lazy(take) {
+                if !take {
+                    self.trace.yielded();
+                    self.stats.yielded();
+                    return Poll::Ready(Ok(Some(None)));
                 }
+                polled = false;
+            }
+            if polled {
+                self.stats.pending();
+                return Poll::Pending;
             }
+            let fut: Pin<&mut (dyn Future<Output = ()> + 'a)> = match self.delegates.last_mut() {
+                Some(Delegate::Body(fut)) => fut.as_mut(),
+                Some(Delegate::Stream(_)) => unreachable!(),
+                None => match &mut self.fut {
+                    Some(fut) => fut.as_mut(),
+                    None => return Poll::Ready(Ok(None)),
+                },
+            };
+            let poll = {
+                let _enter = self.trace.enter();
+                self.stats
+                    .poll(|| catch_unwind(AssertUnwindSafe(|| fut.poll(cx))))
+            };
+            match poll {
+                Ok(Poll::Ready(_)) => {
+                    let slot = self.slot.borrow();
+                    if slot.is_ret_not_awaited() {
+                        let message = format!("{RET_NOT_AWAITED}{}", slot.diagnostics);
+                        drop(slot);
+                        return Poll::Ready(Err(self.panicked(Box::new(message))));
+                    }
+                    drop(slot);
+                    if self.delegates.pop().is_some() {
+                        polled = false;
+                        continue;
+                    }
+                    self.fut = None;
+                    self.state = IterState::Finished;
+                    self.trace.completed();
+                    return Poll::Ready(Ok(self.take_buffered().map(Some)));
+                }
+                Ok(Poll::Pending) => {
+                    self.state = IterState::Suspended;
+                    polled = true;
+                }
+                Err(payload) => return Poll::Ready(Err(self.panicked(payload))),
+            }
+        }
+    }
+
+    fn panicked(&mut self, payload: Box<dyn Any + Send>) -> PanicPayload {
+        self.fut = None;
+        self.delegates.clear();
+        self.state = IterState::Panicked;
+        self.slot.borrow_mut().clear();
+        let e = PanicPayload::new(payload, self.location);
+        self.trace.panicked(&e);
+        e
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let slot = self.slot.borrow();
+        let buffered = slot.len() + (slot.lazy == Lazy::Pending) as usize;
+        drop(slot);
+        let (lower, upper) = self.delegates.iter().fold(
+            (buffered, self.fut.is_none().then_some(buffered)),
+            |(lower, upper), delegate| {
+                let (l, u) = match delegate {
+                    Delegate::Stream(stream) => stream.size_hint(),
+                    Delegate::Body(_) => (0, None),
+                };
+                (
+                    lower.saturating_add(l),
+                    upper.zip(u).and_then(|(a, b)| a.checked_add(b)),
+                )
+            },
+        );
+        (lower, upper)
+    }
+
+    fn take_buffered(&mut self) -> Option<T> {
+        let value = self.slot.borrow_mut().take()?;
+        self.trace.yielded();
+        self.stats.yielded();
+        Some(value)
+    }
+
+    fn is_terminated(&self) -> bool {
+        self.fut.is_none() && self.delegates.is_empty() && self.slot.borrow().is_empty()
+    }
+
+    #[track_caller]
+    fn next_in(&mut self, cx: &mut Context) -> Option<T> {
+        match self.poll_next(cx) {
+            Poll::Ready(value) => value,
+            Poll::Pending => self.panic_not_called(),
+        }
+    }
+
+    /// Advances past the next item without computing it if it is a lazy value.
+    ///
+    /// Returns `false` if the iterator is finished.
+    #[track_caller]
+    fn skip_in(&mut self, cx: &mut Context) -> bool {
+        match self.poll_item(cx, false) {
+            Poll::Ready(Ok(item)) => item.is_some(),
+            Poll::Ready(Err(e)) => e.resume(),
+            Poll::Pending => self.panic_not_called(),
+        }
+    }
+
+    #[track_caller]
+    fn panic_not_called(&self) -> ! {
+        panic!(
+            "`YieldContext::ret` is not called.{}",
+            self.slot.borrow().diagnostics
+        )
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let mut d = f.debug_struct(name);
+        d.field("state", &self.state);
+        #[cfg(feature = "diagnostics")]
+        self.slot.borrow().diagnostics.debug_fields(&mut d);
+        d.finish()
+    }
+}
+
+impl<T> Drop for Data<'_, T> {
+    fn drop(&mut self) {
+        let contents = self.slot.borrow_mut().take_all();
+        drop(contents);
+        while let Some(delegate) = self.delegates.pop() {
+            drop(delegate);
+        }
+        #[cfg(feature = "tracing")]
+        if self.fut.is_some() {
+            let _enter = self.trace.enter();
+            self.trace.dropped();
+            self.fut = None;
         }
     }
 }
@@ -86,6 +486,12 @@ impl<T> Data<'_, T> {
 /// Non-`Send` iterator implemented using async functions.
 ///
 /// This type does not implement `Send`.
+///
+/// # Panics
+///
+/// If the asynchronous function panics, [`Iterator::next`] resumes the panic with the location
+/// where the iterator was created appended to the message, and the iterator is finished.
+/// Use [`try_next`](Self::try_next) to catch the panic instead.
 pub struct LocalIter<'a, T>(Data<'a, T>);
 
 impl<'a, T: 'a> LocalIter<'a, T> {
@@ -101,20 +507,197 @@ impl<'a, T: 'a> LocalIter<'a, T> {
     /// let list: Vec<_> = iter.collect();
     /// assert_eq!(list, vec![1, 2]);
     /// ```
-    pub fn new<Fut: Future<Output = ()> + 'a>(f: impl FnOnce(LocalIterContext<T>) -> Fut) -> Self {
-        let value = Rc::new(RefCell::new(None));
-        let cx = LocalIterContext(Sender(value.clone()));
-        let fut: Pin<Box<dyn Future<Output = ()> + 'a>> = Box::pin(f(cx));
-        let fut = Some(fut);
-        Self(Data { value, fut })
+    #[track_caller]
+    pub fn new<Fut: Future<Output = ()> + 'a>(
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        Self::from_slot(Slot::new(), f)
+    }
+
+    /// Create an iterator from an asynchronous function that can yield up to `capacity` values without suspending.
+    ///
+    /// `ret` stores the value in a buffer and suspends the asynchronous function only when the buffer is full.
+    /// The consumer takes buffered values without resuming the asynchronous function,
+    /// which reduces the overhead per value when the values are small.
+    ///
+    /// [`new`](Self::new) is equivalent to `new_buffered(1, f)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[track_caller]
+    pub fn new_buffered<Fut: Future<Output = ()> + 'a>(
+        capacity: usize,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        Self::from_slot(Slot::with_capacity(capacity), f)
+    }
+
+    /// Create an iterator from an asynchronous function, allocating the function and the value slot with `alloc`.
+    ///
+    /// The allocator must be `'static`, because memory holding a pinned future must not be reused
+    /// before the future is dropped, even if the iterator is leaked.
+    ///
+    /// This method is available with the `allocator-api` feature.
+    #[cfg(feature = "allocator-api")]
+    #[track_caller]
+    pub fn new_in<Fut: Future<Output = ()> + 'a>(
+        alloc: &'static impl Allocator,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        Self::from_slot_in(alloc, Slot::new(), f)
+    }
+
+    #[track_caller]
+    fn from_slot<Fut: Future<Output = ()> + 'a>(
+        slot: Slot<T, Delegate<'a, T>>,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        #[cfg(not(feature = "allocator-api"))]
+        return Self::from_parts(Rc::new(RefCell::new(slot)), |cx| Box::pin(f(cx)));
+        #[cfg(feature = "allocator-api")]
+        return Self::from_slot_in(&Global, slot, f);
+    }
+
+    #[cfg(feature = "allocator-api")]
+    #[track_caller]
+    fn from_slot_in<Fut: Future<Output = ()> + 'a>(
+        alloc: &'static impl Allocator,
+        slot: Slot<T, Delegate<'a, T>>,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        let alloc: LocalAlloc = alloc;
+        Self::from_parts(Rc::new_in(RefCell::new(slot), alloc), |cx| {
+            Box::pin_in(f(cx), alloc)
+        })
+    }
+
+    #[track_caller]
+    fn from_parts(
+        slot: SlotPtr<'a, T>,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> BoxFuture<'a>,
+    ) -> Self {
+        let cx = LocalIterContext(Sender(slot.clone()));
+        let fut = f(cx);
+        Self::from_data(slot, Some(fut), Vec::new())
+    }
+
+    #[track_caller]
+    fn from_data(
+        slot: SlotPtr<'a, T>,
+        fut: Option<BoxFuture<'a>>,
+        delegates: Vec<Delegate<'a, T>>,
+    ) -> Self {
+        Self(Data {
+            slot,
+            fut,
+            delegates,
+            location: Location::caller(),
+            state: IterState::NotStarted,
+            trace: Trace::new(),
+            stats: StatsRecorder::new(),
+        })
     }
 
+    #[track_caller]
     #[allow(clippy::should_implement_trait)]
     pub fn from_iter(iter: impl IntoIterator<Item = T, IntoIter: 'a>) -> Self {
-        let iter = iter.into_iter();
-        Self::new(|mut cx| async move {
-            cx.ret_iter(iter).await;
-        })
+        #[cfg(not(feature = "allocator-api"))]
+        let slot = Rc::new(RefCell::new(Slot::new()));
+        #[cfg(feature = "allocator-api")]
+        let slot = Rc::new_in(RefCell::new(Slot::new()), &Global as LocalAlloc);
+        let delegate = Delegate::Stream(Box::pin(stream::iter(iter)));
+        Self::from_data(slot, None, vec![delegate])
+    }
+}
+
+impl<'a, T> LocalIter<'a, T> {
+    pub(crate) fn into_async(self) -> LocalAsyncIter<'a, T> {
+        LocalAsyncIter(self, Budget::default())
+    }
+}
+
+impl<T> LocalIter<'_, T> {
+    /// Returns the state of this iterator.
+    pub fn state(&self) -> IterState {
+        self.0.state
+    }
+
+    /// Sets the name of this iterator used in panic messages, `Debug` output and the `tracing` span.
+    #[cfg(any(feature = "diagnostics", feature = "tracing"))]
+    pub fn with_name(self, name: impl Into<Cow<'static, str>>) -> Self {
+        let name = name.into();
+        #[cfg(feature = "tracing")]
+        self.0.trace.set_name(&name);
+        #[cfg(feature = "diagnostics")]
+        self.0.slot.borrow_mut().diagnostics.set_name(name);
+        self
+    }
+
+    /// Returns the statistics of this iterator.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.0.stats.get()
+    }
+
+    /// Returns the next value, or the payload of the panic if the asynchronous function panicked.
+    ///
+    /// After a panic, the iterator is finished and subsequent calls return `Ok(None)`.
+    #[track_caller]
+    pub fn try_next(&mut self) -> Result<Option<T>, PanicPayload> {
+        match self
+            .0
+            .poll_try_next(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(value) => value,
+            Poll::Pending => self.0.panic_not_called(),
+        }
+    }
+
+    /// Fills `buf` with the next values and returns the number of values written.
+    ///
+    /// The first `n` elements of `buf` are initialized, where `n` is the returned value.
+    /// If `n` is less than `buf.len()`, the iterator is finished.
+    ///
+    /// The asynchronous function is resumed repeatedly without returning to the caller between values.
+    #[track_caller]
+    pub fn fill(&mut self, buf: &mut [MaybeUninit<T>]) -> usize {
+        let mut cx = Context::from_waker(Waker::noop());
+        for (i, slot) in buf.iter_mut().enumerate() {
+            match self.0.next_in(&mut cx) {
+                Some(value) => {
+                    slot.write(value);
+                }
+                None => return i,
+            }
+        }
+        buf.len()
+    }
+
+    /// Returns an array of the next `N` values.
+    ///
+    /// If the iterator finishes before `N` values are yielded, returns the remaining values as `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yield_return::LocalIter;
+    /// let mut iter = LocalIter::new(|mut y| async move {
+    ///     y.ret_iter(0..5).await;
+    /// });
+    /// assert_eq!(iter.next_chunk::<2>(), Ok([0, 1]));
+    /// assert_eq!(iter.next_chunk::<2>(), Ok([2, 3]));
+    /// assert_eq!(iter.next_chunk::<2>(), Err(vec![4]));
+    /// ```
+    #[track_caller]
+    pub fn next_chunk<const N: usize>(&mut self) -> Result<[T; N], Vec<T>> {
+        let mut cx = Context::from_waker(Waker::noop());
+        let values: [Option<T>; N] = array::from_fn(|_| self.0.next_in(&mut cx));
+        if values.last().is_none_or(Option::is_some) {
+            Ok(values.map(|value| value.unwrap()))
+        } else {
+            Err(values.into_iter().flatten().collect())
+        }
     }
 }
 
@@ -122,35 +705,139 @@ impl<T> Iterator for LocalIter<'_, T> {
     type Item = T;
     #[track_caller]
     fn next(&mut self) -> Option<Self::Item> {
-        match self.0.poll_next(&mut Context::from_waker(Waker::noop())) {
-            Poll::Ready(value) => value,
-            Poll::Pending => panic!("`YieldContext::ret` is not called."),
+        self.0.next_in(&mut Context::from_waker(Waker::noop()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    #[track_caller]
+    fn nth(&mut self, n: usize) -> Option<T> {
+        let mut cx = Context::from_waker(Waker::noop());
+        for _ in 0..n {
+            if !self.0.skip_in(&mut cx) {
+                return None;
+            }
+        }
+        self.0.next_in(&mut cx)
+    }
+
+    #[cfg(feature = "nightly")]
+    #[track_caller]
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let mut cx = Context::from_waker(Waker::noop());
+        for i in 0..n {
+            if !self.0.skip_in(&mut cx) {
+                return NonZero::new(n - i).map_or(Ok(()), Err);
+            }
+        }
+        Ok(())
+    }
+
+    #[track_caller]
+    fn fold<B, F: FnMut(B, T) -> B>(mut self, init: B, mut f: F) -> B {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut acc = init;
+        while let Some(value) = self.0.next_in(&mut cx) {
+            acc = f(acc, value);
+        }
+        acc
+    }
+
+    #[track_caller]
+    fn for_each<F: FnMut(T)>(mut self, mut f: F) {
+        let mut cx = Context::from_waker(Waker::noop());
+        while let Some(value) = self.0.next_in(&mut cx) {
+            f(value);
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[track_caller]
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        F: FnMut(B, T) -> R,
+        R: Try<Output = B>,
+    {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut acc = init;
+        while let Some(value) = self.0.next_in(&mut cx) {
+            acc = f(acc, value)?;
         }
+        R::from_output(acc)
     }
 }
 impl<T> FusedIterator for LocalIter<'_, T> {}
 
+impl<T> fmt::Debug for LocalIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_debug(f, "LocalIter")
+    }
+}
+
 /// Non-`Send` stream context.
 ///
 /// This type does not implement `Send`.
-pub struct LocalAsyncIterContext<T>(LocalIterContext<T>);
+pub struct LocalAsyncIterContext<'a, T>(LocalIterContext<'a, T>);
 
-impl<T> LocalAsyncIterContext<T> {
+impl<'a, T> LocalAsyncIterContext<'a, T> {
     /// Yields all values from a stream.
+    ///
+    /// The asynchronous function is suspended for each value.
+    /// Use [`delegate_stream`](Self::delegate_stream) to hand a `'static` stream over to the consumer instead.
     pub async fn ret_stream(&mut self, stream: impl Stream<Item = T>) {
         let mut stream = pin!(stream);
         while let Some(value) = stream.next().await {
             self.0.ret(value).await;
         }
     }
+
+    /// Yields all values from a stream without resuming the asynchronous function for each value.
+    ///
+    /// `stream` is handed over to the consumer, which polls it directly until it is exhausted.
+    /// The asynchronous function is resumed only after that,
+    /// and the `size_hint` of `stream` is included in the `size_hint` of the consumer.
+    ///
+    /// Unlike [`ret_stream`](Self::ret_stream), `stream` must be `'static`.
+    #[track_caller]
+    pub fn delegate_stream(
+        &mut self,
+        stream: impl Stream<Item = T> + 'static,
+    ) -> impl Future<Output = ()> {
+        (self.0).0.set_delegate(Delegate::Stream(Box::pin(stream)));
+        &mut (self.0).0
+    }
+
+    /// Yields all values from a nested asynchronous function, such as a recursive call.
+    ///
+    /// See [`LocalIterContext::ret_nested`] for details.
+    #[track_caller]
+    pub fn ret_nested<Fut: Future<Output = ()> + 'a>(
+        &mut self,
+        f: impl FnOnce(LocalAsyncIterContext<'a, T>) -> Fut,
+    ) -> impl Future<Output = ()> {
+        self.0.ret_nested(|cx| f(LocalAsyncIterContext(cx)))
+    }
+
+    /// Suspends the body once and lets the executor run other tasks before resuming.
+    pub fn yield_now(&mut self) -> impl Future<Output = ()> {
+        YieldNow::new()
+    }
+}
+impl<T> fmt::Debug for LocalAsyncIterContext<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.0.fmt_debug(f, "LocalAsyncIterContext")
+    }
 }
-impl<T> Deref for LocalAsyncIterContext<T> {
-    type Target = LocalIterContext<T>;
+
+impl<'a, T> Deref for LocalAsyncIterContext<'a, T> {
+    type Target = LocalIterContext<'a, T>;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
-impl<T> DerefMut for LocalAsyncIterContext<T> {
+impl<'a, T> DerefMut for LocalAsyncIterContext<'a, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
@@ -159,7 +846,13 @@ impl<T> DerefMut for LocalAsyncIterContext<T> {
 /// Non-`Send` stream implemented using async functions.
 ///
 /// This type does not implement `Send`.
-pub struct LocalAsyncIter<'a, T>(LocalIter<'a, T>);
+///
+/// # Panics
+///
+/// If the asynchronous function panics, [`Stream::poll_next`] resumes the panic with the location
+/// where the stream was created appended to the message, and the stream is finished.
+/// Use [`try_next`](Self::try_next) to catch the panic instead.
+pub struct LocalAsyncIter<'a, T>(LocalIter<'a, T>, Budget);
 
 impl<'a, T: 'a> LocalAsyncIter<'a, T> {
     /// Create a stream from an asynchronous function.
@@ -176,18 +869,175 @@ impl<'a, T: 'a> LocalAsyncIter<'a, T> {
     /// assert_eq!(list, vec![1, 2]);
     /// # });
     /// ```
+    #[track_caller]
     pub fn new<Fut: Future<Output = ()> + 'a>(
-        f: impl FnOnce(LocalAsyncIterContext<T>) -> Fut,
+        f: impl FnOnce(LocalAsyncIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        Self(
+            LocalIter::new(|cx| f(LocalAsyncIterContext(cx))),
+            Budget::default(),
+        )
+    }
+
+    /// Create a stream from an asynchronous function that can yield up to `capacity` values without suspending.
+    ///
+    /// See [`LocalIter::new_buffered`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[track_caller]
+    pub fn new_buffered<Fut: Future<Output = ()> + 'a>(
+        capacity: usize,
+        f: impl FnOnce(LocalAsyncIterContext<'a, T>) -> Fut,
     ) -> Self {
-        Self(LocalIter::new(|cx| f(LocalAsyncIterContext(cx))))
+        Self(
+            LocalIter::new_buffered(capacity, |cx| f(LocalAsyncIterContext(cx))),
+            Budget::default(),
+        )
     }
 
+    /// Create a stream from an asynchronous function, allocating the function and the value slot with `alloc`.
+    ///
+    /// See [`LocalIter::new_in`] for details.
+    #[cfg(feature = "allocator-api")]
+    #[track_caller]
+    pub fn new_in<Fut: Future<Output = ()> + 'a>(
+        alloc: &'static impl Allocator,
+        f: impl FnOnce(LocalAsyncIterContext<'a, T>) -> Fut,
+    ) -> Self {
+        Self(
+            LocalIter::new_in(alloc, |cx| f(LocalAsyncIterContext(cx))),
+            Budget::default(),
+        )
+    }
+
+    #[track_caller]
     #[allow(clippy::should_implement_trait)]
     pub fn from_iter(iter: impl IntoIterator<Item = T, IntoIter: 'a>) -> Self {
-        let iter = iter.into_iter();
-        Self::new(|mut cx| async move {
-            cx.ret_iter(iter).await;
-        })
+        LocalIter::from_iter(iter).into_async()
+    }
+
+    /// Limits the number of consecutive items returned from `poll_next` without returning `Poll::Pending`.
+    ///
+    /// When the budget is exhausted, `poll_next` wakes the current task and returns `Poll::Pending`,
+    /// giving other tasks on the executor a chance to run.
+    /// The budget is restored each time `poll_next` returns `Poll::Pending`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is 0.
+    #[track_caller]
+    pub fn with_budget(self, budget: usize) -> Self {
+        Self(self.0, Budget::new(budget))
+    }
+}
+
+impl<T: 'static> LocalAsyncIter<'static, T> {
+    /// Create a stream whose asynchronous function runs as a separate task on `spawner`.
+    ///
+    /// The task runs concurrently with the consumer and produces up to `capacity` values ahead of it.
+    /// If the task panics, the panic is propagated to the consumer.
+    /// Dropping the stream cancels the task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use futures::executor::LocalPool;
+    /// use yield_return::LocalAsyncIter;
+    /// let mut pool = LocalPool::new();
+    /// let iter = LocalAsyncIter::spawn_with(pool.spawner(), 2, |mut y| async move {
+    ///     y.ret(1).await;
+    ///     y.ret(2).await;
+    /// })
+    /// .unwrap();
+    /// let list: Vec<_> = pool.run_until(futures::StreamExt::collect(iter));
+    /// assert_eq!(list, vec![1, 2]);
+    /// ```
+    #[track_caller]
+    pub fn spawn_with<Fut: Future<Output = ()> + 'static>(
+        spawner: impl LocalSpawn,
+        capacity: usize,
+        f: impl FnOnce(LocalAsyncIterContext<'static, T>) -> Fut + 'static,
+    ) -> Result<Self, SpawnError> {
+        assert!(capacity > 0, "The capacity must be greater than 0.");
+        let (mut tx, mut rx) = mpsc::channel(capacity - 1);
+        let mut source = LocalAsyncIter::new(f);
+        let (task, handle) = async move {
+            while let Some(value) = source.next().await {
+                if tx.send(value).await.is_err() {
+                    break;
+                }
+            }
+        }
+        .remote_handle();
+        spawner.spawn_local(task)?;
+        Ok(Self::new(|mut y| async move {
+            while let Some(value) = rx.next().await {
+                y.ret(value).await;
+            }
+            handle.await;
+        }))
+    }
+}
+
+impl<T> LocalAsyncIter<'_, T> {
+    /// Returns the state of this stream.
+    pub fn state(&self) -> IterState {
+        self.0.state()
+    }
+
+    /// Sets the name of this stream used in panic messages, `Debug` output and the `tracing` span.
+    #[cfg(any(feature = "diagnostics", feature = "tracing"))]
+    pub fn with_name(self, name: impl Into<Cow<'static, str>>) -> Self {
+        Self(self.0.with_name(name), self.1)
+    }
+
+    /// Returns the statistics of this stream.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.0.stats()
+    }
+
+    /// Polls the next value, or the payload of the panic if the asynchronous function panicked.
+    ///
+    /// After a panic, the stream is terminated and subsequent calls return `Poll::Ready(Ok(None))`.
+    pub fn poll_try_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<T>, PanicPayload>> {
+        self.1.poll_next(cx, |cx| self.0.0.poll_try_next(cx))
+    }
+
+    /// Returns the next value, or the payload of the panic if the asynchronous function panicked.
+    ///
+    /// After a panic, the stream is terminated and subsequent calls return `Ok(None)`.
+    pub async fn try_next(&mut self) -> Result<Option<T>, PanicPayload> {
+        poll_fn(|cx| self.poll_try_next(cx)).await
+    }
+
+    /// Attempts to fill `buf` with the next values, returning the number of values written.
+    ///
+    /// The first `n` elements of `buf` are initialized, where `n` is the returned value.
+    /// Returns `Poll::Ready(n)` with `n > 0` as soon as the stream is not ready or the budget is exhausted
+    /// after at least one value has been written.
+    /// If the budget is exhausted, the next call returns `Poll::Pending`.
+    /// Returns `Poll::Ready(0)` if the stream has finished or `buf` is empty.
+    pub fn poll_fill(&mut self, cx: &mut Context<'_>, buf: &mut [MaybeUninit<T>]) -> Poll<usize> {
+        for (i, slot) in buf.iter_mut().enumerate() {
+            if i > 0 && self.1.is_exhausted() {
+                return Poll::Ready(i);
+            }
+            match self.1.poll_next(cx, |cx| self.0.0.poll_next(cx)) {
+                Poll::Ready(Some(value)) => {
+                    slot.write(value);
+                }
+                Poll::Ready(None) => return Poll::Ready(i),
+                Poll::Pending if i == 0 => return Poll::Pending,
+                Poll::Pending => return Poll::Ready(i),
+            }
+        }
+        Poll::Ready(buf.len())
     }
 }
 
@@ -195,11 +1045,22 @@ impl<T> Stream for LocalAsyncIter<'_, T> {
     type Item = T;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.0.0.poll_next(cx)
+        let this = &mut *self;
+        this.1.poll_next(cx, |cx| this.0.0.poll_next(cx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.0.size_hint()
     }
 }
 impl<T> FusedStream for LocalAsyncIter<'_, T> {
     fn is_terminated(&self) -> bool {
-        self.0.0.fut.is_none()
+        self.0.0.is_terminated()
+    }
+}
+
+impl<T> fmt::Debug for LocalAsyncIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.0.fmt_debug(f, "LocalAsyncIter")
     }
 }
@@ -0,0 +1,354 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    iter::FusedIterator,
+    mem,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    thread::{self, ThreadId},
+};
+
+use crate::{Iter, LocalIter};
+
+enum Source<I> {
+    Idle(I),
+    Producing(ThreadId),
+    Panicked,
+    Done,
+}
+
+enum Step<I: Iterator> {
+    Value(I::Item),
+    End,
+    Pull(I),
+    Wait(ThreadId),
+}
+
+struct Cache<I: Iterator> {
+    source: Source<I>,
+    items: Vec<I::Item>,
+}
+
+impl<I: Iterator<Item: Clone>> Cache<I> {
+    fn new(source: I) -> Self {
+        Self {
+            source: Source::Idle(source),
+            items: Vec::new(),
+        }
+    }
+
+    fn step(&mut self, index: usize) -> Step<I> {
+        if let Some(value) = self.items.get(index) {
+            return Step::Value(value.clone());
+        }
+        match mem::replace(&mut self.source, Source::Done) {
+            Source::Idle(source) => {
+                self.source = Source::Producing(thread::current().id());
+                Step::Pull(source)
+            }
+            Source::Producing(id) => {
+                self.source = Source::Producing(id);
+                Step::Wait(id)
+            }
+            Source::Panicked => {
+                self.source = Source::Panicked;
+                panic!("the source of the memo panicked");
+            }
+            Source::Done => Step::End,
+        }
+    }
+
+    /// Stores the value pulled from `source`, and returns the source if it has ended so that it is dropped by the caller.
+    fn finish(&mut self, source: I, value: Option<I::Item>) -> Option<I> {
+        if let Some(value) = value {
+            self.items.push(value);
+            self.source = Source::Idle(source);
+            None
+        } else {
+            self.source = Source::Done;
+            Some(source)
+        }
+    }
+
+    fn abort(&mut self) {
+        if matches!(self.source, Source::Producing(_)) {
+            self.source = Source::Panicked;
+        }
+    }
+
+    fn size_hint(&self, index: usize) -> (usize, Option<usize>) {
+        let cached = self.items.len().saturating_sub(index);
+        let (lower, upper) = match &self.source {
+            Source::Idle(source) => source.size_hint(),
+            Source::Producing(_) => (0, None),
+            Source::Panicked | Source::Done => (0, Some(0)),
+        };
+        (
+            lower.saturating_add(cached),
+            upper.and_then(|upper| upper.checked_add(cached)),
+        )
+    }
+}
+
+/// `Send` and `Sync` cache of the values of an iterator, which can be iterated many times.
+///
+/// Created by [`Iter::memo`] or by [`Memo::new`] from any iterator.
+///
+/// Each cursor created by [`iter`](Self::iter) replays the cached values and pulls new values from the source
+/// only when it goes beyond the furthest position reached so far.
+///
+/// The source is pulled without holding the lock, so cursors reading cached values never wait for it.
+/// A cursor that goes beyond the cached values while another thread is pulling the source waits for that value.
+/// Going beyond the cached values from within the source itself panics.
+///
+/// If the source panics, the panic is propagated to the cursor that pulled the value,
+/// and any cursor that later goes beyond the cached values panics as well.
+///
+/// # Example
+///
+/// ```
+/// use yield_return::Iter;
+/// let memo = Iter::new(|mut y| async move {
+///     y.ret(1).await;
+///     y.ret(2).await;
+/// })
+/// .memo();
+/// let mut a = memo.iter();
+/// let b = memo.iter();
+/// assert_eq!(a.next(), Some(1));
+/// assert_eq!(b.collect::<Vec<_>>(), vec![1, 2]);
+/// assert_eq!(a.collect::<Vec<_>>(), vec![2]);
+/// ```
+pub struct Memo<I: Iterator> {
+    cache: Mutex<Cache<I>>,
+    produced: Condvar,
+}
+
+impl<I: Iterator<Item: Clone>> Memo<I> {
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            cache: Mutex::new(Cache::new(iter.into_iter())),
+            produced: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache<I>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns a cursor that yields the values from the beginning.
+    pub fn iter(&self) -> MemoIter<'_, I> {
+        MemoIter {
+            memo: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item: Clone>> IntoIterator for &'a Memo<I> {
+    type Item = I::Item;
+    type IntoIter = MemoIter<'a, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<I: Iterator> fmt::Debug for Memo<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memo").finish_non_exhaustive()
+    }
+}
+
+/// Cursor over the values of a [`Memo`].
+pub struct MemoIter<'a, I: Iterator> {
+    memo: &'a Memo<I>,
+    index: usize,
+}
+
+impl<I: Iterator<Item: Clone>> Iterator for MemoIter<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut cache = self.memo.lock();
+        loop {
+            match cache.step(self.index) {
+                Step::Value(value) => {
+                    self.index += 1;
+                    return Some(value);
+                }
+                Step::End => return None,
+                Step::Wait(id) => {
+                    assert!(
+                        id != thread::current().id(),
+                        "the memo is iterated from within its source"
+                    );
+                    cache = self
+                        .memo
+                        .produced
+                        .wait(cache)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+                Step::Pull(mut source) => {
+                    drop(cache);
+                    let guard = AbortGuard(self.memo);
+                    let value = source.next();
+                    let ended = self.memo.lock().finish(source, value);
+                    drop(guard);
+                    drop(ended);
+                    cache = self.memo.lock();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.memo.lock().size_hint(self.index)
+    }
+}
+impl<I: Iterator<Item: Clone>> FusedIterator for MemoIter<'_, I> {}
+
+/// Wakes the cursors waiting for the source, marking it as panicked if the value was not stored.
+struct AbortGuard<'a, I: Iterator<Item: Clone>>(&'a Memo<I>);
+
+impl<I: Iterator<Item: Clone>> Drop for AbortGuard<'_, I> {
+    fn drop(&mut self) {
+        self.0.lock().abort();
+        self.0.produced.notify_all();
+    }
+}
+
+impl<I: Iterator> Clone for MemoIter<'_, I> {
+    fn clone(&self) -> Self {
+        Self {
+            memo: self.memo,
+            index: self.index,
+        }
+    }
+}
+
+impl<I: Iterator> fmt::Debug for MemoIter<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoIter")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Non-`Sync` cache of the values of an iterator, which can be iterated many times.
+///
+/// Created by [`LocalIter::memo`] or by [`LocalMemo::new`] from any iterator.
+///
+/// Each cursor created by [`iter`](Self::iter) replays the cached values and pulls new values from the source
+/// only when it goes beyond the furthest position reached so far.
+///
+/// The source is pulled without borrowing the cache, so the source can read the cached values.
+/// Going beyond the cached values from within the source itself panics.
+///
+/// If the source panics, the panic is propagated to the cursor that pulled the value,
+/// and any cursor that later goes beyond the cached values panics as well.
+pub struct LocalMemo<I: Iterator>(RefCell<Cache<I>>);
+
+impl<I: Iterator<Item: Clone>> LocalMemo<I> {
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self(RefCell::new(Cache::new(iter.into_iter())))
+    }
+
+    /// Returns a cursor that yields the values from the beginning.
+    pub fn iter(&self) -> LocalMemoIter<'_, I> {
+        LocalMemoIter {
+            memo: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item: Clone>> IntoIterator for &'a LocalMemo<I> {
+    type Item = I::Item;
+    type IntoIter = LocalMemoIter<'a, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<I: Iterator> fmt::Debug for LocalMemo<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalMemo").finish_non_exhaustive()
+    }
+}
+
+/// Cursor over the values of a [`LocalMemo`].
+pub struct LocalMemoIter<'a, I: Iterator> {
+    memo: &'a LocalMemo<I>,
+    index: usize,
+}
+
+impl<I: Iterator<Item: Clone>> Iterator for LocalMemoIter<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let step = self.memo.0.borrow_mut().step(self.index);
+            match step {
+                Step::Value(value) => {
+                    self.index += 1;
+                    return Some(value);
+                }
+                Step::End => return None,
+                Step::Wait(_) => panic!("the memo is iterated from within its source"),
+                Step::Pull(mut source) => {
+                    let guard = LocalAbortGuard(self.memo);
+                    let value = source.next();
+                    let ended = self.memo.0.borrow_mut().finish(source, value);
+                    drop(guard);
+                    drop(ended);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.memo.0.borrow().size_hint(self.index)
+    }
+}
+impl<I: Iterator<Item: Clone>> FusedIterator for LocalMemoIter<'_, I> {}
+
+/// Marks the source of a [`LocalMemo`] as panicked if the value was not stored.
+struct LocalAbortGuard<'a, I: Iterator<Item: Clone>>(&'a LocalMemo<I>);
+
+impl<I: Iterator<Item: Clone>> Drop for LocalAbortGuard<'_, I> {
+    fn drop(&mut self) {
+        self.0.0.borrow_mut().abort();
+    }
+}
+
+impl<I: Iterator> Clone for LocalMemoIter<'_, I> {
+    fn clone(&self) -> Self {
+        Self {
+            memo: self.memo,
+            index: self.index,
+        }
+    }
+}
+
+impl<I: Iterator> fmt::Debug for LocalMemoIter<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalMemoIter")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone + Send> Iter<'_, T> {
+    /// Caches the values of this iterator so that they can be iterated many times.
+    pub fn memo(self) -> Memo<Self> {
+        Memo::new(self)
+    }
+}
+
+impl<T: Clone> LocalIter<'_, T> {
+    /// Caches the values of this iterator so that they can be iterated many times.
+    pub fn memo(self) -> LocalMemo<Self> {
+        LocalMemo::new(self)
+    }
+}
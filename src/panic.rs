@@ -0,0 +1,77 @@
+use std::{
+    any::Any,
+    error::Error,
+    fmt,
+    panic::{Location, resume_unwind},
+};
+
+/// Payload of a panic that occurred in the asynchronous function of an iterator.
+///
+/// Returned by `try_next` of each iterator type.
+pub struct PanicPayload {
+    payload: Box<dyn Any + Send>,
+    location: &'static Location<'static>,
+}
+
+impl PanicPayload {
+    pub(crate) fn new(payload: Box<dyn Any + Send>, location: &'static Location<'static>) -> Self {
+        Self { payload, location }
+    }
+
+    /// Returns the location where the iterator was created.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the panic message if the payload is a `&str` or `String`.
+    pub fn message(&self) -> Option<&str> {
+        if let Some(s) = self.payload.downcast_ref::<&str>() {
+            Some(s)
+        } else if let Some(s) = self.payload.downcast_ref::<String>() {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the original panic payload.
+    pub fn into_inner(self) -> Box<dyn Any + Send> {
+        self.payload
+    }
+
+    /// Resumes the panic.
+    ///
+    /// If the payload is a `&str` or `String`, the location where the iterator was created is appended to the message.
+    pub fn resume(self) -> ! {
+        let payload: Box<dyn Any + Send> = if let Some(message) = self.message() {
+            Box::new(format!(
+                "{message}\n(iterator created at {})",
+                self.location
+            ))
+        } else {
+            self.payload
+        };
+        resume_unwind(payload)
+    }
+}
+
+impl fmt::Debug for PanicPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PanicPayload")
+            .field("message", &self.message())
+            .field("location", &self.location)
+            .finish()
+    }
+}
+
+impl fmt::Display for PanicPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "iterator created at {} panicked", self.location)?;
+        if let Some(message) = self.message() {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for PanicPayload {}
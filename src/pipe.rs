@@ -0,0 +1,99 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt, stream};
+
+use crate::{LocalAsyncIter, LocalIter, LocalIterContext};
+
+type Stage<'a, I, O> = Box<dyn FnOnce(PipeInput<'a, I>) -> LocalIter<'a, O> + 'a>;
+
+/// Single-threaded composition of stages implemented using async functions.
+///
+/// Each stage receives its input as a [`PipeInput`] and yields its output using a [`LocalIterContext`].
+/// Stages run in the thread of the consumer, and values are passed from stage to stage without intermediate collections.
+///
+/// # Example
+///
+/// ```
+/// use yield_return::Pipe;
+/// let pipe = Pipe::new(|mut input, mut y| async move {
+///     let mut last = None;
+///     while let Some(value) = input.next().await {
+///         if last != Some(value) {
+///             y.ret(value).await;
+///             last = Some(value);
+///         }
+///     }
+/// })
+/// .then(|mut input, mut y| async move {
+///     while let Some(value) = input.next().await {
+///         y.ret(value * 10).await;
+///     }
+/// });
+/// let list: Vec<_> = pipe.apply([1, 1, 2, 2, 1]).collect();
+/// assert_eq!(list, vec![10, 20, 10]);
+/// ```
+pub struct Pipe<'a, I, O>(Stage<'a, I, O>);
+
+impl<'a, I: 'a, O: 'a> Pipe<'a, I, O> {
+    /// Create a pipe consisting of a single stage.
+    pub fn new<Fut: Future<Output = ()> + 'a>(
+        f: impl FnOnce(PipeInput<'a, I>, LocalIterContext<'a, O>) -> Fut + 'a,
+    ) -> Self {
+        Self(Box::new(|input| LocalIter::new(|cx| f(input, cx))))
+    }
+
+    /// Append a stage that receives the output of this pipe as its input.
+    pub fn then<P: 'a, Fut: Future<Output = ()> + 'a>(
+        self,
+        f: impl FnOnce(PipeInput<'a, O>, LocalIterContext<'a, P>) -> Fut + 'a,
+    ) -> Pipe<'a, I, P> {
+        self.then_pipe(Pipe::new(f))
+    }
+
+    /// Append a pipe that receives the output of this pipe as its input.
+    pub fn then_pipe<P: 'a>(self, next: Pipe<'a, O, P>) -> Pipe<'a, I, P> {
+        Pipe(Box::new(|input| {
+            let upstream = (self.0)(input).into_async();
+            (next.0)(PipeInput::new(upstream))
+        }))
+    }
+
+    /// Apply this pipe to an iterator.
+    ///
+    /// As with [`LocalIter`], stages must not await anything other than
+    /// [`PipeInput::next`] and [`LocalIterContext::ret`].
+    pub fn apply(self, iter: impl IntoIterator<Item = I, IntoIter: 'a>) -> LocalIter<'a, O> {
+        (self.0)(PipeInput::new(stream::iter(iter)))
+    }
+
+    /// Apply this pipe to a stream.
+    pub fn apply_stream(self, stream: impl Stream<Item = I> + 'a) -> LocalAsyncIter<'a, O> {
+        (self.0)(PipeInput::new(stream)).into_async()
+    }
+}
+
+/// Input of a [`Pipe`] stage.
+pub struct PipeInput<'a, T>(Pin<Box<dyn Stream<Item = T> + 'a>>);
+
+impl<'a, T> PipeInput<'a, T> {
+    fn new(stream: impl Stream<Item = T> + 'a) -> Self {
+        Self(Box::pin(stream))
+    }
+
+    /// Receives the next input value. Returns `None` if there are no more values.
+    pub async fn next(&mut self) -> Option<T> {
+        self.0.next().await
+    }
+}
+
+impl<T> Stream for PipeInput<'_, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    any::Any,
+    future::Future,
+    iter::FusedIterator,
+    marker::PhantomData,
+    panic::{AssertUnwindSafe, catch_unwind, resume_unwind},
+    sync::mpsc::{Receiver, SyncSender, sync_channel},
+    thread,
+};
+
+use crate::{LocalIter, LocalIterContext};
+
+type Message<T> = Result<T, Box<dyn Any + Send>>;
+type Jobs<'a> = Vec<Box<dyn FnOnce() + Send + 'a>>;
+type Build<'a, T> = Box<dyn FnOnce(&mut Jobs<'a>) -> Receiver<Message<T>> + 'a>;
+
+/// Multi-threaded pipeline of generator stages.
+///
+/// Each stage runs on its own scoped thread and is connected to the next stage by a bounded queue.
+/// Because the threads are scoped, stages may borrow values from the enclosing scope.
+///
+/// If a stage panics, the panic is propagated through the downstream stages to the consumer.
+///
+/// # Example
+///
+/// ```
+/// use yield_return::Pipeline;
+/// let input = vec!["1", "2", "3"];
+/// let input = &input;
+/// let list: Vec<_> = Pipeline::new(4, |mut y| async move {
+///     for s in input {
+///         y.ret(s.parse::<i32>().unwrap()).await;
+///     }
+/// })
+/// .stage(4, |upstream, mut y| async move {
+///     for value in upstream {
+///         y.ret(value * 10).await;
+///     }
+/// })
+/// .run(|iter| iter.collect());
+/// assert_eq!(list, vec![10, 20, 30]);
+/// ```
+pub struct Pipeline<'a, T> {
+    build: Build<'a, T>,
+}
+
+impl<'a, T: Send + 'a> Pipeline<'a, T> {
+    /// Create a pipeline whose first stage is an asynchronous function.
+    ///
+    /// The stage can produce up to `capacity` values ahead of the next stage.
+    pub fn new<Fut: Future<Output = ()> + 'a>(
+        capacity: usize,
+        f: impl FnOnce(LocalIterContext<'a, T>) -> Fut + Send + 'a,
+    ) -> Self {
+        Self {
+            build: Box::new(move |jobs| spawn_stage(jobs, capacity, f)),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter(
+        capacity: usize,
+        iter: impl IntoIterator<Item = T, IntoIter: Send + 'a>,
+    ) -> Self {
+        let iter = iter.into_iter();
+        Self::new(capacity, |mut cx| async move {
+            cx.ret_iter(iter).await;
+        })
+    }
+
+    /// Append a stage that receives the values of the previous stage as `upstream`.
+    ///
+    /// The stage can produce up to `capacity` values ahead of the next stage.
+    pub fn stage<U: Send + 'a, Fut: Future<Output = ()> + 'a>(
+        self,
+        capacity: usize,
+        f: impl FnOnce(PipelineIter<'a, T>, LocalIterContext<'a, U>) -> Fut + Send + 'a,
+    ) -> Pipeline<'a, U> {
+        Pipeline {
+            build: Box::new(move |jobs| {
+                let upstream = PipelineIter((self.build)(jobs), PhantomData);
+                spawn_stage(jobs, capacity, move |cx| f(upstream, cx))
+            }),
+        }
+    }
+
+    /// Run all stages and consume the values of the last stage with `f`.
+    ///
+    /// Returns after `f` returns and all stage threads have finished.
+    /// `f` cannot return the [`PipelineIter`], because the stages would wait forever for it to be consumed.
+    ///
+    /// ```compile_fail
+    /// use yield_return::Pipeline;
+    /// let iter = Pipeline::new(1, |mut y| async move {
+    ///     y.ret(1).await;
+    /// })
+    /// .run(|iter| iter.take(2));
+    /// ```
+    pub fn run<R>(self, f: impl for<'s> FnOnce(PipelineIter<'s, T>) -> R) -> R {
+        let mut jobs = Vec::new();
+        let iter = PipelineIter((self.build)(&mut jobs), PhantomData);
+        thread::scope(|s| {
+            for job in jobs {
+                s.spawn(job);
+            }
+            f(iter)
+        })
+    }
+}
+
+fn spawn_stage<'a, T: Send + 'a, Fut: Future<Output = ()> + 'a>(
+    jobs: &mut Jobs<'a>,
+    capacity: usize,
+    f: impl FnOnce(LocalIterContext<'a, T>) -> Fut + Send + 'a,
+) -> Receiver<Message<T>> {
+    let (tx, rx) = sync_channel(capacity);
+    jobs.push(Box::new(move || run_stage(&tx, f)));
+    rx
+}
+
+fn run_stage<'a, T: 'a, Fut: Future<Output = ()> + 'a>(
+    tx: &SyncSender<Message<T>>,
+    f: impl FnOnce(LocalIterContext<'a, T>) -> Fut,
+) {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        for value in LocalIter::new(f) {
+            if tx.send(Ok(value)).is_err() {
+                break;
+            }
+        }
+    }));
+    if let Err(e) = result {
+        let _ = tx.send(Err(e));
+    }
+}
+
+/// Iterator over the values produced by a stage of a [`Pipeline`].
+///
+/// If the stage panics, [`Iterator::next`] resumes the panic.
+///
+/// The lifetime `'s` ties the iterator to [`Pipeline::run`] or [`Pipeline::stage`], so that it cannot outlive the stages.
+pub struct PipelineIter<'s, T>(Receiver<Message<T>>, PhantomData<&'s ()>);
+
+impl<T> Iterator for PipelineIter<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.0.recv() {
+            Ok(Ok(value)) => Some(value),
+            Ok(Err(e)) => resume_unwind(e),
+            Err(_) => None,
+        }
+    }
+}
+impl<T> FusedIterator for PipelineIter<'_, T> {}
@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+
+use serde::{
+    Serialize, Serializer,
+    ser::{Error, SerializeMap, SerializeSeq},
+};
+
+use crate::{Iter, LocalIter};
+
+const ALREADY_SERIALIZED: &str = "the iterator has already been serialized";
+
+/// [`Serialize`] implementation that serializes the values of an iterator as a sequence of unknown length.
+///
+/// Created by [`Iter::into_seq`] and [`LocalIter::into_seq`], or by [`IterSeq::new`] from any iterator.
+///
+/// The iterator is consumed by the first serialization. Serializing again returns an error.
+///
+/// # Example
+///
+/// ```
+/// use yield_return::Iter;
+/// let iter = Iter::new(|mut y| async move {
+///     y.ret(1).await;
+///     y.ret(2).await;
+/// });
+/// let json = serde_json::to_string(&iter.into_seq()).unwrap();
+/// assert_eq!(json, "[1,2]");
+/// ```
+pub struct IterSeq<I>(RefCell<Option<I>>);
+
+impl<I: Iterator<Item: Serialize>> IterSeq<I> {
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self(RefCell::new(Some(iter.into_iter())))
+    }
+}
+
+impl<I: Iterator<Item: Serialize>> Serialize for IterSeq<I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let iter = self
+            .0
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| S::Error::custom(ALREADY_SERIALIZED))?;
+        let mut seq = serializer.serialize_seq(None)?;
+        for value in iter {
+            seq.serialize_element(&value)?;
+        }
+        seq.end()
+    }
+}
+
+/// [`Serialize`] implementation that serializes the key-value pairs of an iterator as a map of unknown length.
+///
+/// Created by [`Iter::into_map`] and [`LocalIter::into_map`], or by [`IterMap::new`] from any iterator.
+///
+/// The iterator is consumed by the first serialization. Serializing again returns an error.
+///
+/// # Example
+///
+/// ```
+/// use yield_return::Iter;
+/// let iter = Iter::new(|mut y| async move {
+///     y.ret(("a", 1)).await;
+///     y.ret(("b", 2)).await;
+/// });
+/// let json = serde_json::to_string(&iter.into_map()).unwrap();
+/// assert_eq!(json, r#"{"a":1,"b":2}"#);
+/// ```
+pub struct IterMap<I>(RefCell<Option<I>>);
+
+impl<K, V, I> IterMap<I>
+where
+    K: Serialize,
+    V: Serialize,
+    I: Iterator<Item = (K, V)>,
+{
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self(RefCell::new(Some(iter.into_iter())))
+    }
+}
+
+impl<K, V, I> Serialize for IterMap<I>
+where
+    K: Serialize,
+    V: Serialize,
+    I: Iterator<Item = (K, V)>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let iter = self
+            .0
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| S::Error::custom(ALREADY_SERIALIZED))?;
+        let mut map = serializer.serialize_map(None)?;
+        for (key, value) in iter {
+            map.serialize_entry(&key, &value)?;
+        }
+        map.end()
+    }
+}
+
+impl<T: Serialize> Iter<'_, T> {
+    /// Converts this iterator into a [`Serialize`] implementation that serializes the values as a sequence.
+    pub fn into_seq(self) -> IterSeq<Self> {
+        IterSeq::new(self)
+    }
+}
+
+impl<K: Serialize, V: Serialize> Iter<'_, (K, V)> {
+    /// Converts this iterator into a [`Serialize`] implementation that serializes the pairs as a map.
+    pub fn into_map(self) -> IterMap<Self> {
+        IterMap::new(self)
+    }
+}
+
+impl<T: Serialize> LocalIter<'_, T> {
+    /// Converts this iterator into a [`Serialize`] implementation that serializes the values as a sequence.
+    pub fn into_seq(self) -> IterSeq<Self> {
+        IterSeq::new(self)
+    }
+}
+
+impl<K: Serialize, V: Serialize> LocalIter<'_, (K, V)> {
+    /// Converts this iterator into a [`Serialize`] implementation that serializes the pairs as a map.
+    pub fn into_map(self) -> IterMap<Self> {
+        IterMap::new(self)
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use crate::diagnostics::Diagnostics;
+
+pub(crate) const RET_NOT_AWAITED: &str = "The result of `ret` is not await.";
+
+/// State of a value yielded by `ret_with` whose closure has not been called yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Lazy {
+    /// No lazy value is pending.
+    None,
+    /// A lazy value is pending and the consumer has not decided whether to take it.
+    Pending,
+    /// The consumer takes the value, so the closure must be called.
+    Take,
+    /// The consumer skips the value, so the closure must be dropped without being called.
+    Skip,
+}
+
+/// State shared between an iterator and its context.
+///
+/// Holds up to `capacity` values yielded by `ret` that have not been taken by the consumer.
+/// The first value is stored inline, so no buffer is allocated when `capacity` is 1.
+///
+/// `delegate` holds an iterator, stream or nested function of type `D` handed over by the context
+/// until the consumer takes it.
+///
+/// `lazy` tracks a value yielded by `ret_with`, which always follows the buffered values.
+///
+/// `detached` is `true` if the last value was stored by a writer, which does not wait for the consumer to take it.
+/// Such a value may still be buffered when the asynchronous function completes, and is yielded afterwards.
+pub(crate) struct Slot<T, D> {
+    value: Option<T>,
+    rest: VecDeque<T>,
+    capacity: usize,
+    delegate: Option<D>,
+    pub lazy: Lazy,
+    pub detached: bool,
+    pub diagnostics: Diagnostics,
+}
+
+impl<T, D> Slot<T, D> {
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::with_capacity(1)
+    }
+
+    #[track_caller]
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        Self {
+            value: None,
+            rest: VecDeque::with_capacity(capacity - 1),
+            capacity,
+            delegate: None,
+            lazy: Lazy::None,
+            detached: false,
+            diagnostics: Diagnostics::new(),
+        }
+    }
+
+    #[track_caller]
+    pub fn set(&mut self, value: T) {
+        assert!(!self.is_blocked(), "{RET_NOT_AWAITED}{}", self.diagnostics);
+        self.push(value);
+        self.detached = false;
+        self.diagnostics.record_ret();
+    }
+
+    /// Stores a value written by a writer, which is yielded even if the asynchronous function completes first.
+    #[track_caller]
+    pub fn set_detached(&mut self, value: T) {
+        self.set(value);
+        self.detached = true;
+    }
+
+    /// Stores a value without checking the capacity, such as the value of a lazy value the consumer takes.
+    pub fn push(&mut self, value: T) {
+        if self.value.is_none() {
+            self.value = Some(value);
+        } else {
+            self.rest.push_back(value);
+        }
+    }
+
+    #[track_caller]
+    pub fn set_delegate(&mut self, delegate: D) {
+        assert!(!self.is_blocked(), "{RET_NOT_AWAITED}{}", self.diagnostics);
+        self.delegate = Some(delegate);
+        self.detached = false;
+        self.diagnostics.record_ret();
+    }
+
+    #[track_caller]
+    pub fn set_lazy(&mut self) {
+        assert!(!self.is_blocked(), "{RET_NOT_AWAITED}{}", self.diagnostics);
+        self.lazy = Lazy::Pending;
+        self.detached = false;
+        self.diagnostics.record_ret();
+    }
+
+    /// Decides whether the pending lazy value is taken or skipped.
+    ///
+    /// Returns `false` if no lazy value is pending.
+    pub fn resolve_lazy(&mut self, take: bool) -> bool {
+        if self.lazy != Lazy::Pending {
+            return false;
+        }
+        self.lazy = if take { Lazy::Take } else { Lazy::Skip };
+        true
+    }
+
+    pub fn take_delegate(&mut self) -> Option<D> {
+        self.delegate.take()
+    }
+
+    pub fn take(&mut self) -> Option<T> {
+        let value = self.value.take();
+        self.value = self.rest.pop_front();
+        value
+    }
+
+    /// Removes the delegate and the buffered values so that they can be dropped after the slot is unlocked.
+    ///
+    /// The delegate may hold a context that refers to the slot, so it must be removed to break the cycle.
+    pub fn take_all(&mut self) -> (Option<D>, Vec<T>) {
+        let values = self
+            .value
+            .take()
+            .into_iter()
+            .chain(self.rest.drain(..))
+            .collect();
+        self.lazy = Lazy::None;
+        self.detached = false;
+        (self.delegate.take(), values)
+    }
+
+    pub fn clear(&mut self) {
+        self.value = None;
+        self.rest.clear();
+        self.delegate = None;
+        self.lazy = Lazy::None;
+        self.detached = false;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none()
+    }
+
+    pub fn len(&self) -> usize {
+        self.value.is_some() as usize + self.rest.len()
+    }
+
+    /// Returns `true` if the future returned by `ret` or a delegation method must not complete yet.
+    pub fn is_blocked(&self) -> bool {
+        self.len() >= self.capacity || self.delegate.is_some() || self.lazy != Lazy::None
+    }
+
+    /// Returns `true` if the asynchronous function completed without awaiting the result of `ret`.
+    pub fn is_ret_not_awaited(&self) -> bool {
+        self.is_blocked() && !self.detached
+    }
+}
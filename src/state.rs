@@ -0,0 +1,16 @@
+/// State of an iterator.
+///
+/// Returned by `state` of each iterator type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IterState {
+    /// The asynchronous function has not been polled yet.
+    NotStarted,
+    /// The asynchronous function is suspended and will be resumed by the next poll.
+    Suspended,
+    /// The asynchronous function has completed.
+    ///
+    /// Iterators created with `new_buffered` may still hold values yielded before completion.
+    Finished,
+    /// The asynchronous function has panicked.
+    Panicked,
+}
@@ -0,0 +1,82 @@
+#[cfg(feature = "stats")]
+use std::time::{Duration, Instant};
+
+/// Statistics of an iterator.
+///
+/// Returned by `stats` of each iterator type.
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Number of values yielded.
+    pub items: u64,
+    /// Number of times the asynchronous function was polled.
+    pub polls: u64,
+    /// Number of polls that returned `Poll::Pending` without yielding a value.
+    pub pending_polls: u64,
+    /// Cumulative time spent polling the asynchronous function.
+    pub body_time: Duration,
+    /// Cumulative time between yielding a value and the next poll by the consumer.
+    pub consumer_time: Duration,
+}
+
+/// Records [`Stats`] with the `stats` feature.
+///
+/// Without the `stats` feature, this type is empty and records nothing.
+#[cfg(feature = "stats")]
+pub(crate) struct StatsRecorder {
+    stats: Stats,
+    last_yield: Option<Instant>,
+}
+
+#[cfg(feature = "stats")]
+impl StatsRecorder {
+    pub fn new() -> Self {
+        Self {
+            stats: Stats::default(),
+            last_yield: None,
+        }
+    }
+
+    pub fn poll<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        if let Some(last_yield) = self.last_yield.take() {
+            self.stats.consumer_time += start - last_yield;
+        }
+        self.stats.polls += 1;
+        let result = f();
+        self.stats.body_time += start.elapsed();
+        result
+    }
+
+    pub fn yielded(&mut self) {
+        self.stats.items += 1;
+        self.last_yield = Some(Instant::now());
+    }
+
+    pub fn pending(&mut self) {
+        self.stats.pending_polls += 1;
+    }
+
+    pub fn get(&self) -> Stats {
+        self.stats
+    }
+}
+
+#[cfg(not(feature = "stats"))]
+pub(crate) struct StatsRecorder;
+
+#[cfg(not(feature = "stats"))]
+impl StatsRecorder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn poll<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+
+    pub fn yielded(&mut self) {}
+
+    pub fn pending(&mut self) {}
+}
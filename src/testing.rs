@@ -0,0 +1,211 @@
+//! Utilities for testing asynchronous functions used as iterator bodies.
+//!
+//! This module is available with the `testing` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use yield_return::AsyncIter;
+//! use yield_return::testing::{Step, Stepper, mock_future};
+//!
+//! let (fut, handle) = mock_future();
+//! let iter = AsyncIter::new(|mut y| async move {
+//!     y.ret(1).await;
+//!     let value = fut.await;
+//!     y.ret(value).await;
+//! });
+//! let mut s = Stepper::new(iter);
+//! s.assert_steps([Step::Yield(1), Step::Pending]);
+//! assert!(handle.is_waiting());
+//! handle.complete(2);
+//! assert_eq!(s.wake_count(), 1);
+//! s.assert_steps([Step::Yield(2), Step::Done]);
+//! ```
+use std::{
+    fmt::Debug,
+    future::Future,
+    panic::{AssertUnwindSafe, catch_unwind, resume_unwind},
+    pin::Pin,
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use futures::{Stream, StreamExt};
+
+use crate::slot::RET_NOT_AWAITED;
+
+/// Result of polling a stream once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step<T> {
+    /// The stream yielded a value.
+    Yield(T),
+    /// The stream returned `Poll::Pending`.
+    Pending,
+    /// The stream finished.
+    Done,
+}
+
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Polls a stream one step at a time with a waker that counts wake-ups.
+pub struct Stepper<S> {
+    stream: S,
+    waker: Arc<CountingWaker>,
+    steps: usize,
+}
+
+impl<S: Stream + Unpin> Stepper<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            waker: Arc::new(CountingWaker(AtomicUsize::new(0))),
+            steps: 0,
+        }
+    }
+
+    /// Polls the stream once.
+    pub fn step(&mut self) -> Step<S::Item> {
+        let waker = Waker::from(self.waker.clone());
+        self.steps += 1;
+        match self
+            .stream
+            .poll_next_unpin(&mut Context::from_waker(&waker))
+        {
+            Poll::Ready(Some(value)) => Step::Yield(value),
+            Poll::Ready(None) => Step::Done,
+            Poll::Pending => Step::Pending,
+        }
+    }
+
+    /// Polls the stream once for each element of `expected` and asserts that the results match.
+    #[track_caller]
+    pub fn assert_steps(&mut self, expected: impl IntoIterator<Item = Step<S::Item>>)
+    where
+        S::Item: Debug + PartialEq,
+    {
+        for expected in expected {
+            let index = self.steps;
+            let actual = self.step();
+            assert_eq!(actual, expected, "step {index}");
+        }
+    }
+
+    /// Returns the number of times the waker passed to the stream was woken.
+    pub fn wake_count(&self) -> usize {
+        self.waker.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of times the stream was polled.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+/// Asserts that `iter` yields exactly the values of `expected` and then finishes.
+#[track_caller]
+pub fn assert_yields<T: Debug + PartialEq>(
+    iter: impl IntoIterator<Item = T>,
+    expected: impl IntoIterator<Item = T>,
+) {
+    let mut iter = iter.into_iter();
+    for (index, expected) in expected.into_iter().enumerate() {
+        assert_eq!(iter.next(), Some(expected), "value {index}");
+    }
+    assert_eq!(iter.next(), None, "the iterator yields extra values");
+}
+
+/// Collects all values of `iter`, or returns the panic message if a future returned by `ret` was not awaited.
+///
+/// Other panics are resumed.
+pub fn check_ret_awaited<I: IntoIterator>(iter: I) -> Result<Vec<I::Item>, String> {
+    match catch_unwind(AssertUnwindSafe(|| iter.into_iter().collect())) {
+        Ok(values) => Ok(values),
+        Err(payload) => {
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                resume_unwind(payload)
+            };
+            if message.contains(RET_NOT_AWAITED) {
+                Err(message)
+            } else {
+                resume_unwind(payload)
+            }
+        }
+    }
+}
+
+struct MockState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Create a future that completes when [`MockHandle::complete`] is called.
+pub fn mock_future<T>() -> (MockFuture<T>, MockHandle<T>) {
+    let state = Arc::new(Mutex::new(MockState {
+        value: None,
+        waker: None,
+    }));
+    (MockFuture(state.clone()), MockHandle(state))
+}
+
+/// Future created by [`mock_future`].
+pub struct MockFuture<T>(Arc<Mutex<MockState<T>>>);
+
+impl<T> Future for MockFuture<T> {
+    type Output = T;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = lock(&self.0);
+        if let Some(value) = state.value.take() {
+            state.waker = None;
+            Poll::Ready(value)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Handle to complete a [`MockFuture`].
+pub struct MockHandle<T>(Arc<Mutex<MockState<T>>>);
+
+impl<T> MockHandle<T> {
+    /// Completes the future with `value` and wakes the task waiting for it.
+    pub fn complete(&self, value: T) {
+        let waker = {
+            let mut state = lock(&self.0);
+            state.value = Some(value);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if the future has been polled and is waiting for [`complete`](Self::complete).
+    pub fn is_waiting(&self) -> bool {
+        lock(&self.0).waker.is_some()
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "tracing")]
+use std::panic::Location;
+
+use crate::PanicPayload;
+
+/// Span and events of an iterator emitted with the `tracing` feature.
+///
+/// Without the `tracing` feature, this type is empty and emits nothing.
+#[cfg(feature = "tracing")]
+pub(crate) struct Trace(tracing::Span);
+
+#[cfg(feature = "tracing")]
+impl Trace {
+    #[track_caller]
+    pub fn new() -> Self {
+        Self(tracing::debug_span!(
+            "yield_return",
+            name = tracing::field::Empty,
+            location = %Location::caller(),
+        ))
+    }
+
+    pub fn set_name(&self, name: &str) {
+        self.0.record("name", name);
+    }
+
+    pub fn enter(&self) -> tracing::span::Entered<'_> {
+        self.0.enter()
+    }
+
+    pub fn yielded(&self) {
+        self.0.in_scope(|| tracing::trace!("yield"));
+    }
+
+    pub fn completed(&self) {
+        self.0.in_scope(|| tracing::debug!("complete"));
+    }
+
+    pub fn dropped(&self) {
+        self.0
+            .in_scope(|| tracing::debug!("drop before completion"));
+    }
+
+    pub fn panicked(&self, e: &PanicPayload) {
+        self.0
+            .in_scope(|| tracing::error!(message = e.message(), "panic"));
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Trace;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Entered;
+
+#[cfg(not(feature = "tracing"))]
+impl Trace {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn enter(&self) -> Entered {
+        Entered
+    }
+
+    pub fn yielded(&self) {}
+
+    pub fn completed(&self) {}
+
+    pub fn panicked(&self, _e: &PanicPayload) {}
+}
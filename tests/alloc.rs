@@ -0,0 +1,104 @@
+#![feature(allocator_api)]
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use futures::{StreamExt, executor::block_on};
+use yield_return::{AsyncIter, Iter, LocalAsyncIter, LocalIter};
+
+struct CountingAlloc {
+    allocated: AtomicUsize,
+    live: AtomicUsize,
+}
+
+impl CountingAlloc {
+    const fn new() -> Self {
+        Self {
+            allocated: AtomicUsize::new(0),
+            live: AtomicUsize::new(0),
+        }
+    }
+    fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::SeqCst)
+    }
+    fn live(&self) -> usize {
+        self.live.load(Ordering::SeqCst)
+    }
+}
+
+unsafe impl Allocator for CountingAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocated.fetch_add(1, Ordering::SeqCst);
+        self.live.fetch_add(1, Ordering::SeqCst);
+        Global.allocate(layout)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.fetch_sub(1, Ordering::SeqCst);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn iter_new_in() {
+    static ALLOC: CountingAlloc = CountingAlloc::new();
+    let iter = Iter::new_in(&ALLOC, |mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_eq!(ALLOC.allocated(), 2);
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![1, 2]);
+    assert_eq!(ALLOC.live(), 0);
+}
+
+#[test]
+fn local_iter_new_in() {
+    static ALLOC: CountingAlloc = CountingAlloc::new();
+    let values = [1, 2];
+    let iter = LocalIter::new_in(&ALLOC, |mut y| async move {
+        y.ret_iter(values.iter().copied()).await;
+    });
+    assert_eq!(ALLOC.allocated(), 2);
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![1, 2]);
+    assert_eq!(ALLOC.live(), 0);
+}
+
+#[test]
+fn drop_before_completion() {
+    static ALLOC: CountingAlloc = CountingAlloc::new();
+    let mut iter = Iter::new_in(&ALLOC, |mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(ALLOC.live(), 2);
+    drop(iter);
+    assert_eq!(ALLOC.live(), 0);
+}
+
+#[test]
+fn async_iter_new_in() {
+    static ALLOC: CountingAlloc = CountingAlloc::new();
+    let iter = AsyncIter::new_in(&ALLOC, |mut y| async move {
+        y.ret(1).await;
+    });
+    assert_eq!(ALLOC.allocated(), 2);
+    let list: Vec<_> = block_on(iter.collect());
+    assert_eq!(list, vec![1]);
+    assert_eq!(ALLOC.live(), 0);
+}
+
+#[test]
+fn local_async_iter_new_in() {
+    static ALLOC: CountingAlloc = CountingAlloc::new();
+    let iter = LocalAsyncIter::new_in(&ALLOC, |mut y| async move {
+        y.ret(1).await;
+    });
+    assert_eq!(ALLOC.allocated(), 2);
+    let list: Vec<_> = block_on(iter.collect());
+    assert_eq!(list, vec![1]);
+    assert_eq!(ALLOC.live(), 0);
+}
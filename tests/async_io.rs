@@ -0,0 +1,125 @@
+use std::{future::pending, io::Write, time::Duration};
+
+use futures::{
+    AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, StreamExt, TryStreamExt, poll, stream,
+};
+use rt_local::runtime::core::test;
+use yield_return::{AsyncIter, AsyncIterReader, LocalAsyncIter};
+
+mod utils;
+use utils::sleep;
+
+#[test]
+async fn read_to_end() {
+    let iter = AsyncIter::new(|mut y| async move {
+        y.ret(b"abc".to_vec()).await;
+        y.ret(Vec::new()).await;
+        sleep(Duration::from_millis(10)).await;
+        y.ret(b"de".to_vec()).await;
+    });
+    let mut buf = Vec::new();
+    iter.into_reader().read_to_end(&mut buf).await.unwrap();
+    assert_eq!(buf, b"abcde");
+}
+
+#[test]
+async fn read_small_buffer() {
+    let iter = LocalAsyncIter::new(|mut y| async move {
+        y.ret(&b"abc"[..]).await;
+        y.ret(&b"de"[..]).await;
+    });
+    let mut r = iter.into_reader();
+    let mut buf = [0; 2];
+    assert_eq!(r.read(&mut buf).await.unwrap(), 2);
+    assert_eq!(&buf, b"ab");
+    assert_eq!(r.read(&mut buf).await.unwrap(), 1);
+    assert_eq!(&buf[..1], b"c");
+    assert_eq!(r.read(&mut buf).await.unwrap(), 2);
+    assert_eq!(&buf, b"de");
+    assert_eq!(r.read(&mut buf).await.unwrap(), 0);
+}
+
+#[test]
+async fn buf_read_lines() {
+    let r = AsyncIterReader::new(stream::iter(["ab\nc", "d\n", "e"]));
+    let lines: Vec<_> = r.lines().try_collect().await.unwrap();
+    assert_eq!(lines, vec!["ab", "cd", "e"]);
+}
+
+#[test]
+async fn async_writer() {
+    let iter = AsyncIter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.async_writer();
+        w.write_all(b"abc").await.unwrap();
+        sleep(Duration::from_millis(10)).await;
+        w.write_all(b"").await.unwrap();
+        w.write_all(b"de").await.unwrap();
+        w.flush().await.unwrap();
+    });
+    let list: Vec<_> = iter.collect().await;
+    assert_eq!(list, vec![b"abc".to_vec(), b"de".to_vec()]);
+}
+
+#[test]
+async fn local_async_writer() {
+    let iter = LocalAsyncIter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.async_writer();
+        w.write_all(b"a").await.unwrap();
+        w.flush().await.unwrap();
+        y.ret(b"b".to_vec()).await;
+        let mut w = y.async_writer();
+        w.write_all(b"c").await.unwrap();
+        w.close().await.unwrap();
+    });
+    let list: Vec<_> = iter.collect().await;
+    assert_eq!(list, vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+}
+
+#[test]
+async fn async_writer_cancel() {
+    let iter = AsyncIter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.async_writer();
+        w.write_all(b"a").await.unwrap();
+        {
+            let mut write = w.write(b"b");
+            assert!(poll!(&mut write).is_pending());
+        }
+        w.write_all(b"c").await.unwrap();
+        w.close().await.unwrap();
+    });
+    let list: Vec<_> = iter.collect().await;
+    assert_eq!(list, vec![b"a".to_vec(), b"c".to_vec()]);
+}
+
+#[test]
+async fn writer_dropped_while_suspended() {
+    let mut iter = AsyncIter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.writer();
+        w.write_all(b"abc").unwrap();
+        pending::<()>().await;
+        w.finish().await;
+    });
+    assert!(poll!(iter.next()).is_pending());
+    drop(iter);
+}
+
+#[test]
+async fn async_writer_without_close() {
+    let iter = AsyncIter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.async_writer();
+        w.write_all(b"ab").await.unwrap();
+        w.write_all(b"c").await.unwrap();
+    });
+    let list: Vec<_> = iter.collect().await;
+    assert_eq!(list, vec![b"ab".to_vec(), b"c".to_vec()]);
+}
+
+#[test]
+async fn local_async_writer_without_close() {
+    let mut iter = LocalAsyncIter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.async_writer();
+        w.write_all(b"abc").await.unwrap();
+    });
+    assert_eq!(iter.try_next().await.unwrap(), Some(b"abc".to_vec()));
+    assert_eq!(iter.try_next().await.unwrap(), None);
+}
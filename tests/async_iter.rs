@@ -1,9 +1,23 @@
-use std::{cell::Cell, time::Duration};
+use std::{
+    cell::Cell,
+    future::poll_fn,
+    mem::MaybeUninit,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
 
-use futures::{StreamExt, stream};
+use futures::{
+    Stream, StreamExt,
+    executor::{LocalPool, ThreadPool},
+    stream::{self, FusedStream},
+};
 use rt_local::runtime::core::test;
-use utils::sleep;
-use yield_return::AsyncIter;
+use utils::{counting_waker, sleep};
+use yield_return::{AsyncIter, IterState};
 
 mod utils;
 
@@ -115,3 +129,307 @@ async fn ret_not_sync() {
     let list: Vec<_> = iter.collect().await;
     assert_eq!(list, vec![Cell::new(1)]);
 }
+
+#[test]
+fn budget() {
+    let iter = AsyncIter::new(|mut y| async move {
+        for i in 0..3 {
+            y.ret(i).await;
+        }
+    });
+    let mut iter = iter.with_budget(2);
+    let (waker, count) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+async fn budget_collect() {
+    let iter = AsyncIter::new(|mut y| async move {
+        for i in 0..10 {
+            y.ret(i).await;
+        }
+    });
+    let list: Vec<_> = iter.with_budget(3).collect().await;
+    assert_eq!(list, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn budget_zero() {
+    let _ = AsyncIter::<u32>::new(|mut _y| async move {}).with_budget(0);
+}
+
+#[test]
+fn yield_now() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+        y.yield_now().await;
+        y.ret(2).await;
+    });
+    let (waker, count) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(iter.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn spawn_with() {
+    let mut pool = LocalPool::new();
+    let iter = AsyncIter::spawn_with(pool.spawner(), 2, |mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    })
+    .unwrap();
+    let list: Vec<_> = pool.run_until(iter.collect());
+    assert_eq!(list, vec![1, 2]);
+}
+
+#[test]
+fn spawn_with_capacity() {
+    let mut pool = LocalPool::new();
+    let produced = Arc::new(AtomicUsize::new(0));
+    let mut iter = AsyncIter::spawn_with(pool.spawner(), 2, {
+        let produced = produced.clone();
+        |mut y| async move {
+            for i in 0..10 {
+                produced.fetch_add(1, Ordering::SeqCst);
+                y.ret(i).await;
+            }
+        }
+    })
+    .unwrap();
+    pool.run_until_stalled();
+    assert_eq!(produced.load(Ordering::SeqCst), 2);
+    assert_eq!(pool.run_until(iter.next()), Some(0));
+    pool.run_until_stalled();
+    assert_eq!(produced.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+#[should_panic(expected = "body panicked")]
+fn spawn_with_panic() {
+    let mut pool = LocalPool::new();
+    let iter = AsyncIter::spawn_with(pool.spawner(), 1, |mut y| async move {
+        y.ret(1).await;
+        panic!("body panicked");
+    })
+    .unwrap();
+    let _: Vec<_> = pool.run_until(iter.collect());
+}
+
+#[test]
+fn spawn_with_drop() {
+    struct SetOnDrop(Arc<AtomicBool>);
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+    let mut pool = LocalPool::new();
+    let dropped = Arc::new(AtomicBool::new(false));
+    let mut iter = AsyncIter::spawn_with(pool.spawner(), 1, {
+        let dropped = dropped.clone();
+        |mut y| async move {
+            let _guard = SetOnDrop(dropped);
+            loop {
+                y.ret(1).await;
+            }
+        }
+    })
+    .unwrap();
+    assert_eq!(pool.run_until(iter.next()), Some(1));
+    drop(iter);
+    pool.run_until_stalled();
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn spawn_with_thread_pool() {
+    let pool = ThreadPool::new().unwrap();
+    let iter = AsyncIter::spawn_with(pool, 4, |mut y| async move {
+        for i in 0..100 {
+            y.ret(i).await;
+        }
+    })
+    .unwrap();
+    let list: Vec<_> = futures::executor::block_on(iter.collect());
+    assert_eq!(list, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+async fn try_next() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+        panic!("body panicked");
+    });
+    assert_eq!(iter.try_next().await.unwrap(), Some(1));
+    let e = iter.try_next().await.unwrap_err();
+    assert_eq!(e.message(), Some("body panicked"));
+    assert!(iter.is_terminated());
+    assert_eq!(iter.next().await, None);
+}
+
+#[test]
+async fn state() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+    });
+    assert_eq!(iter.state(), IterState::NotStarted);
+    assert_eq!(iter.next().await, Some(1));
+    assert_eq!(iter.state(), IterState::Suspended);
+    assert_eq!(iter.next().await, None);
+    assert_eq!(iter.state(), IterState::Finished);
+}
+
+#[test]
+async fn state_panicked() {
+    let mut iter = AsyncIter::<u32>::new(|_| async move {
+        panic!("body panicked");
+    });
+    assert!(iter.try_next().await.is_err());
+    assert_eq!(iter.state(), IterState::Panicked);
+}
+
+#[test]
+async fn debug() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        assert!(format!("{y:?}").starts_with("AsyncIterContext {"));
+        y.ret(1).await;
+    });
+    assert_eq!(iter.next().await, Some(1));
+    assert!(format!("{iter:?}").starts_with("AsyncIter { state: Suspended"));
+}
+
+#[test]
+async fn buffered() {
+    let iter = AsyncIter::new_buffered(4, |mut y| async move {
+        for i in 0..10 {
+            y.ret(i).await;
+            if i % 3 == 0 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        }
+    });
+    let list: Vec<_> = iter.collect().await;
+    assert_eq!(list, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+async fn buffered_is_terminated() {
+    let mut iter = AsyncIter::new_buffered(2, |mut y| async move {
+        y.ret(1).await;
+    });
+    assert!(!iter.is_terminated());
+    assert_eq!(iter.next().await, Some(1));
+    assert!(iter.is_terminated());
+    let mut iter = AsyncIter::new_buffered(3, |mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_eq!(iter.next().await, Some(1));
+    assert_eq!(iter.state(), IterState::Finished);
+    assert!(!iter.is_terminated());
+    assert_eq!(iter.next().await, Some(2));
+    assert!(iter.is_terminated());
+}
+
+#[test]
+async fn poll_fill() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+        sleep(Duration::from_millis(10)).await;
+        y.ret(3).await;
+    });
+    let mut buf = [const { MaybeUninit::uninit() }; 4];
+    assert_eq!(poll_fn(|cx| iter.poll_fill(cx, &mut buf)).await, 2);
+    assert_eq!(
+        unsafe { [buf[0].assume_init(), buf[1].assume_init()] },
+        [1, 2]
+    );
+    assert_eq!(poll_fn(|cx| iter.poll_fill(cx, &mut buf)).await, 1);
+    assert_eq!(unsafe { buf[0].assume_init() }, 3);
+    assert_eq!(poll_fn(|cx| iter.poll_fill(cx, &mut buf)).await, 0);
+}
+
+#[test]
+fn poll_fill_budget() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        y.ret_iter(0..5).await;
+    })
+    .with_budget(2);
+    let (waker, count) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut buf = [const { MaybeUninit::uninit() }; 5];
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Ready(2));
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Pending);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Ready(2));
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Pending);
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Ready(1));
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Pending);
+    assert_eq!(iter.poll_fill(&mut cx, &mut buf), Poll::Ready(0));
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+async fn delegate_stream() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut iter = AsyncIter::new({
+        let count = count.clone();
+        |mut y| async move {
+            count.fetch_add(1, Ordering::SeqCst);
+            y.delegate_stream(stream::iter(0..3).then(|v| async move {
+                sleep(Duration::from_millis(10)).await;
+                v
+            }))
+            .await;
+            count.fetch_add(1, Ordering::SeqCst);
+            y.ret(3).await;
+        }
+    });
+    assert_eq!(iter.next().await, Some(0));
+    assert_eq!(iter.size_hint(), (2, None));
+    assert_eq!(iter.next().await, Some(1));
+    assert_eq!(iter.next().await, Some(2));
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert_eq!(iter.next().await, Some(3));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert_eq!(iter.next().await, None);
+    assert!(iter.is_terminated());
+}
+
+#[test]
+async fn ret_nested() {
+    let iter = AsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret_nested(|mut y| async move {
+            sleep(Duration::from_millis(10)).await;
+            y.ret(2).await;
+            y.delegate_stream(stream::iter(3..5)).await;
+        })
+        .await;
+        y.ret(5).await;
+    });
+    assert_eq!(iter.collect::<Vec<_>>().await, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+async fn ret_with() {
+    let iter = AsyncIter::new(|mut y| async move {
+        y.ret_with(|| 1).await;
+        sleep(Duration::from_millis(10)).await;
+        y.ret_with(|| 2).await;
+    });
+    assert_eq!(iter.collect::<Vec<_>>().await, vec![1, 2]);
+}
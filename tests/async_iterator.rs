@@ -0,0 +1,63 @@
+#![feature(async_iterator, gen_blocks)]
+use std::{async_iter::AsyncIterator, future::poll_fn, pin::pin, time::Duration};
+
+use futures::{StreamExt, executor::block_on};
+use yield_return::{AsyncIter, LocalAsyncIter};
+
+mod utils;
+use utils::sleep;
+
+#[test]
+fn from_async_gen() {
+    block_on(async {
+        let iter = AsyncIter::from_async_iter(async gen {
+            yield 1;
+            sleep(Duration::from_millis(10)).await;
+            yield 2;
+        });
+        let list: Vec<_> = iter.collect().await;
+        assert_eq!(list, vec![1, 2]);
+    });
+}
+
+#[test]
+fn local_from_async_gen_borrow() {
+    block_on(async {
+        let values = vec![1, 2];
+        let iter = LocalAsyncIter::from_async_iter(async gen {
+            for value in &values {
+                yield *value * 10;
+            }
+        });
+        let list: Vec<_> = iter.collect().await;
+        assert_eq!(list, vec![10, 20]);
+    });
+}
+
+#[test]
+fn async_iterator() {
+    block_on(async {
+        let iter = AsyncIter::new(|mut y| async move {
+            y.ret(1).await;
+            y.ret(2).await;
+        });
+        let mut iter = pin!(iter);
+        let mut list = Vec::new();
+        while let Some(value) = poll_fn(|cx| AsyncIterator::poll_next(iter.as_mut(), cx)).await {
+            list.push(value);
+        }
+        assert_eq!(list, vec![1, 2]);
+    });
+}
+
+#[test]
+fn local_round_trip() {
+    block_on(async {
+        let iter = LocalAsyncIter::from_async_iter(LocalAsyncIter::new(|mut y| async move {
+            y.ret(1).await;
+            y.ret(2).await;
+        }));
+        let list: Vec<_> = iter.collect().await;
+        assert_eq!(list, vec![1, 2]);
+    });
+}
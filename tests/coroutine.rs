@@ -0,0 +1,66 @@
+#![feature(coroutines, coroutine_trait, gen_blocks)]
+use std::{
+    ops::{Coroutine, CoroutineState},
+    pin::Pin,
+};
+
+use yield_return::{Iter, LocalIter};
+
+#[test]
+fn from_coroutine() {
+    let iter = Iter::from_coroutine(
+        #[coroutine]
+        || {
+            yield 1;
+            yield 2;
+        },
+    );
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![1, 2]);
+}
+
+#[test]
+fn local_from_coroutine_borrow() {
+    let values = vec![1, 2, 3];
+    let iter = LocalIter::from_coroutine(
+        #[coroutine]
+        || {
+            for value in &values {
+                yield *value * 10;
+            }
+        },
+    );
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![10, 20, 30]);
+}
+
+#[test]
+fn from_gen_block() {
+    let iter = LocalIter::from_iter(gen {
+        yield 1;
+        yield 2;
+    });
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![1, 2]);
+}
+
+#[test]
+fn resume_iter() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+    });
+    let mut co = Pin::new(&mut iter);
+    assert_eq!(co.as_mut().resume(()), CoroutineState::Yielded(1));
+    assert_eq!(co.as_mut().resume(()), CoroutineState::Complete(()));
+    assert_eq!(co.as_mut().resume(()), CoroutineState::Complete(()));
+}
+
+#[test]
+fn round_trip() {
+    let iter = LocalIter::from_coroutine(LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    }));
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![1, 2]);
+}
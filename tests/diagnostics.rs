@@ -0,0 +1,91 @@
+#![cfg(feature = "diagnostics")]
+
+use std::{
+    future::pending,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use yield_return::{AsyncIter, Iter, LocalAsyncIter, LocalIter};
+
+fn panic_message(f: impl FnOnce()) -> String {
+    let payload = catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+    if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        payload.downcast_ref::<&str>().unwrap().to_string()
+    }
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn no_await_iter() {
+    let line = line!() + 1;
+    let iter = Iter::new(|mut y| async move {
+        y.ret(1);
+        y.ret(2);
+    })
+    .with_name("numbers");
+    let message = panic_message(|| {
+        let _: Vec<_> = iter.collect();
+    });
+    assert!(message.contains("The result of `ret` is not await."));
+    assert!(message.contains("`numbers`"));
+    assert!(message.contains(&format!("created at tests/diagnostics.rs:{line}")));
+    assert!(message.contains(&format!("last `ret` at tests/diagnostics.rs:{}", line + 1)));
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn no_await_local_iter() {
+    let line = line!() + 1;
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret(1);
+    })
+    .with_name("numbers");
+    let message = panic_message(|| {
+        let _: Vec<_> = iter.collect();
+    });
+    assert!(message.contains("The result of `ret` is not await."));
+    assert!(message.contains("`numbers`"));
+    assert!(message.contains(&format!("created at tests/diagnostics.rs:{line}")));
+    assert!(message.contains(&format!("last `ret` at tests/diagnostics.rs:{}", line + 1)));
+}
+
+#[test]
+fn not_called() {
+    let line = line!() + 1;
+    let mut iter = Iter::<u32>::new(|mut y| async move {
+        y.ret(1).await;
+        pending::<()>().await;
+    });
+    iter.next();
+    let message = panic_message(|| {
+        iter.next();
+    });
+    assert!(message.contains("`YieldContext::ret` is not called."));
+    assert!(message.contains(&format!("created at tests/diagnostics.rs:{line}")));
+    assert!(message.contains(&format!("last `ret` at tests/diagnostics.rs:{}", line + 1)));
+}
+
+#[test]
+fn debug() {
+    let line = line!() + 1;
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+    })
+    .with_name("numbers");
+    iter.next();
+    let s = format!("{iter:?}");
+    assert!(s.starts_with("LocalIter {"));
+    assert!(s.contains("\"numbers\""));
+    assert!(s.contains(&format!("tests/diagnostics.rs:{line}")));
+    assert!(s.contains(&format!("tests/diagnostics.rs:{}", line + 1)));
+}
+
+#[test]
+fn debug_async() {
+    let iter = AsyncIter::<u32>::new(|_| async move {}).with_name("a");
+    assert!(format!("{iter:?}").starts_with("AsyncIter {"));
+    let iter = LocalAsyncIter::<u32>::new(|_| async move {}).with_name("b");
+    assert!(format!("{iter:?}").starts_with("LocalAsyncIter {"));
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "http-body")]
+use std::{future::poll_fn, pin::Pin};
+
+use http::HeaderMap;
+use http_body::{Body, Frame};
+use rt_local::runtime::core::test;
+use yield_return::AsyncIter;
+
+async fn next_frame<B: Body + Unpin>(body: &mut B) -> Option<Result<Frame<B::Data>, B::Error>> {
+    poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx)).await
+}
+
+fn assert_sync<T: Sync>(_: &T) {}
+
+#[test]
+async fn data_and_trailers() {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("x-checksum", "123".parse().unwrap());
+    let expected = trailers.clone();
+    let mut body = AsyncIter::new(|mut y| async move {
+        y.ret(Frame::data(&b"abc"[..])).await;
+        y.ret(Frame::data(&b"de"[..])).await;
+        y.ret(Frame::trailers(trailers)).await;
+    })
+    .into_body();
+    assert!(!body.is_end_stream());
+    let frame = next_frame(&mut body).await.unwrap().unwrap();
+    assert_eq!(frame.into_data().unwrap(), b"abc");
+    let frame = next_frame(&mut body).await.unwrap().unwrap();
+    assert_eq!(frame.into_data().unwrap(), b"de");
+    let frame = next_frame(&mut body).await.unwrap().unwrap();
+    assert_eq!(frame.into_trailers().unwrap(), expected);
+    assert!(next_frame(&mut body).await.is_none());
+    assert!(body.is_end_stream());
+}
+
+#[test]
+async fn size_hint() {
+    let mut body = AsyncIter::new(|mut y| async move {
+        y.ret(Frame::data(&b"abc"[..])).await;
+        y.ret(Frame::data(&b"de"[..])).await;
+    })
+    .into_body()
+    .with_exact_size(5);
+    assert_eq!(body.size_hint().exact(), Some(5));
+    next_frame(&mut body).await;
+    assert_eq!(body.size_hint().exact(), Some(2));
+    next_frame(&mut body).await;
+    assert_eq!(body.size_hint().exact(), Some(0));
+    assert!(next_frame(&mut body).await.is_none());
+    assert_eq!(body.size_hint().exact(), Some(0));
+}
+
+#[test]
+async fn size_hint_default() {
+    let body = AsyncIter::<Frame<&[u8]>>::new(|_| async {}).into_body();
+    assert_eq!(body.size_hint().lower(), 0);
+    assert_eq!(body.size_hint().upper(), None);
+}
+
+#[test]
+async fn sync_body() {
+    let mut body = AsyncIter::new(|mut y| async move {
+        y.ret(Frame::data(&b"abc"[..])).await;
+    })
+    .into_sync_body()
+    .with_exact_size(3);
+    assert_sync(&body);
+    assert!(!body.is_end_stream());
+    let frame = next_frame(&mut body).await.unwrap().unwrap();
+    assert_eq!(frame.into_data().unwrap(), b"abc");
+    assert_eq!(body.size_hint().exact(), Some(0));
+    assert!(next_frame(&mut body).await.is_none());
+    assert!(body.is_end_stream());
+}
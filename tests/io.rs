@@ -0,0 +1,101 @@
+use std::io::{BufRead, Read, Write};
+
+use yield_return::{Iter, IterReader, LocalIter};
+
+#[test]
+fn read_to_end() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret(b"abc".to_vec()).await;
+        y.ret(Vec::new()).await;
+        y.ret(b"de".to_vec()).await;
+    });
+    let mut buf = Vec::new();
+    iter.into_reader().read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"abcde");
+}
+
+#[test]
+fn read_small_buffer() {
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret(&b"abc"[..]).await;
+        y.ret(&b"de"[..]).await;
+    });
+    let mut r = iter.into_reader();
+    let mut buf = [0; 2];
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf, b"ab");
+    assert_eq!(r.read(&mut buf).unwrap(), 1);
+    assert_eq!(&buf[..1], b"c");
+    assert_eq!(r.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf, b"de");
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn read_large_buffer() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret(b"abc").await;
+        y.ret(b"def").await;
+    });
+    let mut r = iter.into_reader();
+    let mut buf = [0; 16];
+    assert_eq!(r.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"abc");
+}
+
+#[test]
+fn buf_read_lines() {
+    let iter = IterReader::new(["ab\nc", "d\n", "e"]);
+    let lines: Vec<_> = iter.lines().map(|l| l.unwrap()).collect();
+    assert_eq!(lines, vec!["ab", "cd", "e"]);
+}
+
+#[test]
+fn buf_read_consume() {
+    let mut r = IterReader::new([b"abc", b"def"]);
+    assert_eq!(r.fill_buf().unwrap(), b"abc");
+    r.consume(2);
+    assert_eq!(r.fill_buf().unwrap(), b"c");
+    r.consume(1);
+    assert_eq!(r.fill_buf().unwrap(), b"def");
+    r.consume(3);
+    assert_eq!(r.fill_buf().unwrap(), b"");
+}
+
+#[test]
+fn writer() {
+    let iter = Iter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.writer();
+        write!(w, "a{}", 1).unwrap();
+        w.write_all(b"b").unwrap();
+        w.ret_buffered().await;
+        w.ret_buffered().await;
+        w.write_all(b"c").unwrap();
+        w.finish().await;
+    });
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list, vec![b"a1b".to_vec(), b"c".to_vec()]);
+}
+
+#[test]
+fn local_writer() {
+    let iter = LocalIter::<Box<[u8]>>::new(|mut y| async move {
+        let mut w = y.writer();
+        w.write_all(b"abc").unwrap();
+        w.finish().await;
+    });
+    let mut s = String::new();
+    iter.into_reader().read_to_string(&mut s).unwrap();
+    assert_eq!(s, "abc");
+}
+
+#[test]
+fn writer_panic() {
+    let mut iter = Iter::<Vec<u8>>::new(|mut y| async move {
+        let mut w = y.writer();
+        w.write_all(b"abc").unwrap();
+        panic!("error");
+    });
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.next(), None);
+}
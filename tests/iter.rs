@@ -1,6 +1,15 @@
-use std::{cell::Cell, future::pending};
+use std::{
+    cell::Cell,
+    future::pending,
+    mem::MaybeUninit,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
-use yield_return::Iter;
+use yield_return::{Iter, IterContext, IterState};
 
 #[test]
 fn no_value() {
@@ -63,6 +72,23 @@ fn use_pending() {
     let _: Vec<_> = iter.collect();
 }
 
+#[test]
+#[allow(unused_must_use)]
+fn try_next_no_await() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1);
+    });
+    let e = iter.try_next().unwrap_err();
+    assert!(
+        e.message()
+            .unwrap()
+            .starts_with("The result of `ret` is not await.")
+    );
+    assert_eq!(iter.state(), IterState::Panicked);
+    assert_eq!(iter.try_next().unwrap(), None);
+    assert_eq!(iter.next(), None);
+}
+
 #[test]
 #[allow(unused_must_use)]
 #[should_panic]
@@ -101,3 +127,400 @@ fn ret_not_sync() {
     let list: Vec<_> = iter.collect();
     assert_eq!(list, vec![Cell::new(1)]);
 }
+
+#[test]
+fn panic_resumed_with_location() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        panic!("body panicked");
+    });
+    assert_eq!(iter.next(), Some(1));
+    let payload = catch_unwind(AssertUnwindSafe(|| iter.next())).unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.contains("body panicked"));
+    assert!(message.contains("iter.rs"));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn try_next() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        panic!("body panicked");
+    });
+    assert_eq!(iter.try_next().unwrap(), Some(1));
+    let e = iter.try_next().unwrap_err();
+    assert_eq!(e.message(), Some("body panicked"));
+    assert!(e.location().file().ends_with("iter.rs"));
+    assert_eq!(iter.try_next().unwrap(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn no_await_then_fused() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1);
+        y.ret(2);
+    });
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn state() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+    });
+    assert_eq!(iter.state(), IterState::NotStarted);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.state(), IterState::Suspended);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.state(), IterState::Finished);
+}
+
+#[test]
+fn state_panicked() {
+    let mut iter = Iter::<u32>::new(|_| async move {
+        panic!("body panicked");
+    });
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.state(), IterState::Panicked);
+}
+
+#[test]
+fn debug() {
+    let mut iter = Iter::new(|mut y| async move {
+        assert!(format!("{y:?}").starts_with("IterContext {"));
+        y.ret(1).await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    assert!(format!("{iter:?}").starts_with("Iter { state: Suspended"));
+}
+
+#[test]
+fn buffered() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut iter = Iter::new_buffered(3, {
+        let count = count.clone();
+        |mut y| async move {
+            for i in 0..5 {
+                count.fetch_add(1, Ordering::SeqCst);
+                y.ret(i).await;
+            }
+        }
+    });
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(count.load(Ordering::SeqCst), 5);
+    assert_eq!(iter.state(), IterState::Finished);
+    assert_eq!(iter.next(), Some(4));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn buffered_zero_capacity() {
+    let _ = Iter::<u32>::new_buffered(0, |_| async {});
+}
+
+#[test]
+#[allow(unused_must_use)]
+#[should_panic(expected = "The result of `ret` is not await.")]
+fn buffered_no_await() {
+    let iter = Iter::new_buffered(2, |mut y| async move {
+        y.ret(1);
+        y.ret(2);
+        y.ret(3);
+    });
+    let _: Vec<_> = iter.collect();
+}
+
+#[test]
+fn fill() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret_iter(0..5).await;
+    });
+    let mut buf = [const { MaybeUninit::uninit() }; 3];
+    assert_eq!(iter.fill(&mut buf), 3);
+    assert_eq!(buf.map(|v| unsafe { v.assume_init() }), [0, 1, 2]);
+    let mut buf = [const { MaybeUninit::uninit() }; 3];
+    assert_eq!(iter.fill(&mut buf), 2);
+    assert_eq!(
+        unsafe { [buf[0].assume_init(), buf[1].assume_init()] },
+        [3, 4]
+    );
+    assert_eq!(iter.fill(&mut buf), 0);
+}
+
+#[test]
+fn next_chunk() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret_iter(0..3).await;
+    });
+    assert_eq!(iter.next_chunk::<2>(), Ok([0, 1]));
+    assert_eq!(iter.next_chunk::<2>(), Err(vec![2]));
+    assert_eq!(iter.next_chunk::<2>(), Err(vec![]));
+    assert_eq!(iter.next_chunk::<0>(), Ok([]));
+}
+
+#[test]
+fn fold() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret_iter(1..=4).await;
+    });
+    assert_eq!(iter.fold(1, |acc, v| acc * 10 + v), 11234);
+}
+
+#[test]
+fn for_each() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret_iter(1..=3).await;
+    });
+    let mut list = Vec::new();
+    iter.for_each(|v| list.push(v));
+    assert_eq!(list, vec![1, 2, 3]);
+}
+
+#[test]
+fn try_fold() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret_iter(1..=5).await;
+    });
+    let r = iter.try_fold(0, |acc, v| if v < 3 { Some(acc + v) } else { None });
+    assert_eq!(r, None);
+    assert_eq!(iter.next(), Some(4));
+    assert_eq!(iter.try_fold(0, |acc, v| Some(acc + v)), Some(5));
+}
+
+#[test]
+#[should_panic(expected = "`YieldContext::ret` is not called.")]
+fn fold_pending() {
+    let iter = Iter::<u32>::new(|_| pending());
+    iter.fold((), |_, _| ());
+}
+
+#[test]
+fn delegate_iter() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut iter = Iter::new({
+        let count = count.clone();
+        |mut y| async move {
+            count.fetch_add(1, Ordering::SeqCst);
+            y.ret(0).await;
+            count.fetch_add(1, Ordering::SeqCst);
+            y.delegate_iter(1..1000).await;
+            count.fetch_add(1, Ordering::SeqCst);
+            y.ret(1000).await;
+        }
+    });
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert_eq!(iter.size_hint(), (998, None));
+    assert_eq!(iter.by_ref().take(998).count(), 998);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert_eq!(iter.next(), Some(1000));
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn delegate_iter_panic() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.delegate_iter((0..3).map(|v| if v < 1 { v } else { panic!("error") }))
+            .await;
+    });
+    assert_eq!(iter.try_next().ok(), Some(Some(0)));
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.state(), IterState::Panicked);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn from_iter_size_hint() {
+    let mut iter = Iter::from_iter::<std::ops::Range<u32>>(0..5);
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.state(), IterState::NotStarted);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.state(), IterState::Suspended);
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(iter.state(), IterState::Finished);
+}
+
+struct Node {
+    value: u32,
+    children: Vec<Node>,
+}
+
+#[allow(clippy::manual_async_fn)]
+fn walk<'a>(mut y: IterContext<'a, u32>, node: &'a Node) -> impl Future<Output = ()> + Send {
+    async move {
+        y.ret(node.value).await;
+        for child in &node.children {
+            y.ret_nested(|y| walk(y, child)).await;
+        }
+    }
+}
+
+#[test]
+fn ret_nested() {
+    let leaf = |value| Node {
+        value,
+        children: Vec::new(),
+    };
+    let tree = Node {
+        value: 1,
+        children: vec![
+            Node {
+                value: 2,
+                children: vec![leaf(3), leaf(4)],
+            },
+            leaf(5),
+        ],
+    };
+    let iter = Iter::new(|y| walk(y, &tree));
+    assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn ret_nested_drop_order() {
+    struct Guard<'a>(&'a Mutex<Vec<&'static str>>, &'static str);
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.0.lock().unwrap().push(self.1);
+        }
+    }
+    let dropped = Mutex::new(Vec::new());
+    let dropped = &dropped;
+    let mut iter = Iter::new(|mut y| async move {
+        let _guard = Guard(dropped, "outer");
+        y.ret_nested(|mut y| async move {
+            let _guard = Guard(dropped, "inner");
+            y.ret(1).await;
+            y.ret(2).await;
+        })
+        .await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    drop(iter);
+    assert_eq!(*dropped.lock().unwrap(), ["inner", "outer"]);
+}
+
+#[test]
+fn ret_nested_deep() {
+    #[allow(clippy::manual_async_fn)]
+    fn count_down(mut y: IterContext<'_, u32>, n: u32) -> impl Future<Output = ()> + Send {
+        async move {
+            if n > 0 {
+                y.ret(n).await;
+                y.ret_nested(move |y| count_down(y, n - 1)).await;
+            }
+        }
+    }
+    let iter = Iter::new(|y| count_down(y, 100_000));
+    assert_eq!(iter.count(), 100_000);
+}
+
+#[test]
+fn ret_nested_panic() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret_nested(|mut y| async move {
+            y.ret(2).await;
+            panic!("error");
+        })
+        .await;
+        y.ret(3).await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), Some(2));
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.state(), IterState::Panicked);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn ret_nested_drop() {
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let guard = Guard(dropped.clone());
+    let mut iter = Iter::new_buffered(2, |mut y| async move {
+        y.ret(1).await;
+        y.ret_nested(move |mut y| async move {
+            let _guard = guard;
+            y.ret(2).await;
+            y.ret(3).await;
+        })
+        .await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    drop(iter);
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn ret_with() {
+    let computed = Arc::new(AtomicUsize::new(0));
+    let iter = Iter::new({
+        let computed = computed.clone();
+        |mut y| async move {
+            for i in 0..10 {
+                let computed = computed.clone();
+                y.ret_with(move || {
+                    computed.fetch_add(1, Ordering::SeqCst);
+                    i
+                })
+                .await;
+            }
+        }
+    });
+    let list: Vec<_> = iter.skip(7).collect();
+    assert_eq!(list, vec![7, 8, 9]);
+    assert_eq!(computed.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn nth() {
+    let computed = AtomicUsize::new(0);
+    let computed = &computed;
+    let mut iter = Iter::new_buffered(2, |mut y| async move {
+        y.ret(0).await;
+        for i in 1..5 {
+            y.ret_with(|| {
+                computed.fetch_add(1, Ordering::SeqCst);
+                i
+            })
+            .await;
+        }
+        y.ret(5).await;
+    });
+    assert_eq!(iter.nth(3), Some(3));
+    assert_eq!(computed.load(Ordering::SeqCst), 1);
+    assert_eq!(iter.next(), Some(4));
+    assert_eq!(iter.nth(10), None);
+    assert_eq!(computed.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn ret_with_size_hint() {
+    let mut iter = Iter::new_buffered(2, |mut y| async move {
+        y.ret(0).await;
+        y.ret_with(|| 1).await;
+    });
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.size_hint(), (1, None));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
@@ -0,0 +1,86 @@
+use std::{cell::Cell, rc::Rc};
+
+use futures::StreamExt;
+use rt_local::runtime::core::test;
+use yield_return::{AsyncIterFn, BoxIterFn, BoxLocalIterFn, IterFn, LocalAsyncIterFn, LocalIterFn};
+
+#[test]
+fn iter_fn() {
+    let iter_fn = IterFn::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    let mut list = Vec::new();
+    for value in &iter_fn {
+        list.push(value);
+    }
+    for value in &iter_fn {
+        list.push(value);
+    }
+    assert_eq!(list, vec![1, 2, 1, 2]);
+}
+
+#[test]
+fn iter_fn_clone() {
+    let n = 3;
+    let iter_fn = IterFn::new(move |mut y| async move {
+        y.ret_iter(0..n).await;
+    });
+    let cloned = iter_fn.clone();
+    assert_eq!(iter_fn.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(cloned.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+struct Numbers {
+    items: BoxIterFn<'static, u32>,
+}
+
+#[test]
+fn iter_fn_boxed() {
+    let numbers = Numbers {
+        items: IterFn::new(|mut y| async move {
+            y.ret(1).await;
+        })
+        .boxed(),
+    };
+    assert_eq!(numbers.items.iter().sum::<u32>(), 1);
+    assert_eq!(numbers.items.iter().sum::<u32>(), 1);
+}
+
+#[test]
+fn local_iter_fn() {
+    let runs = Rc::new(Cell::new(0));
+    let iter_fn: BoxLocalIterFn<u32> = LocalIterFn::new({
+        let runs = runs.clone();
+        move |mut y| {
+            let runs = runs.clone();
+            async move {
+                runs.set(runs.get() + 1);
+                y.ret(runs.get()).await;
+            }
+        }
+    })
+    .boxed();
+    assert_eq!((&iter_fn).into_iter().collect::<Vec<_>>(), vec![1]);
+    assert_eq!((&iter_fn).into_iter().collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+async fn async_iter_fn() {
+    let iter_fn = AsyncIterFn::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_eq!(iter_fn.iter().collect::<Vec<_>>().await, vec![1, 2]);
+    assert_eq!(iter_fn.iter().collect::<Vec<_>>().await, vec![1, 2]);
+}
+
+#[test]
+async fn local_async_iter_fn() {
+    let iter_fn = LocalAsyncIterFn::new(|mut y| async move {
+        y.ret(Rc::new(1)).await;
+    })
+    .boxed();
+    assert_eq!(iter_fn.iter().collect::<Vec<_>>().await, vec![Rc::new(1)]);
+    assert_eq!(iter_fn.iter().collect::<Vec<_>>().await, vec![Rc::new(1)]);
+}
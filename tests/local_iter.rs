@@ -1,6 +1,13 @@
-use std::{cell::Cell, future::pending, ptr::null};
+use std::{
+    cell::{Cell, RefCell},
+    future::pending,
+    mem::MaybeUninit,
+    panic::{AssertUnwindSafe, catch_unwind},
+    ptr::null,
+    rc::Rc,
+};
 
-use yield_return::LocalIter;
+use yield_return::{IterState, LocalIter, LocalIterContext};
 
 #[test]
 fn no_value() {
@@ -63,6 +70,23 @@ fn use_pending() {
     let _: Vec<_> = iter.collect();
 }
 
+#[test]
+#[allow(unused_must_use)]
+fn try_next_no_await() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.ret(1);
+    });
+    let e = iter.try_next().unwrap_err();
+    assert!(
+        e.message()
+            .unwrap()
+            .starts_with("The result of `ret` is not await.")
+    );
+    assert_eq!(iter.state(), IterState::Panicked);
+    assert_eq!(iter.try_next().unwrap(), None);
+    assert_eq!(iter.next(), None);
+}
+
 #[test]
 #[allow(unused_must_use)]
 #[should_panic]
@@ -107,3 +131,269 @@ fn ret_not_sync() {
     let list: Vec<_> = iter.collect();
     assert_eq!(list, vec![Cell::new(1)]);
 }
+
+#[test]
+fn panic_resumed_with_location() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+        panic!("body panicked");
+    });
+    assert_eq!(iter.next(), Some(1));
+    let payload = catch_unwind(AssertUnwindSafe(|| iter.next())).unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.contains("body panicked"));
+    assert!(message.contains("local_iter.rs"));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn try_next() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+        panic!("body panicked");
+    });
+    assert_eq!(iter.try_next().unwrap(), Some(1));
+    let e = iter.try_next().unwrap_err();
+    assert_eq!(e.message(), Some("body panicked"));
+    assert!(e.location().file().ends_with("local_iter.rs"));
+    assert_eq!(iter.try_next().unwrap(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn no_await_then_fused() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.ret(1);
+        y.ret(2);
+    });
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn state() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+    });
+    assert_eq!(iter.state(), IterState::NotStarted);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.state(), IterState::Suspended);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.state(), IterState::Finished);
+}
+
+#[test]
+fn state_panicked() {
+    let mut iter = LocalIter::<u32>::new(|_| async move {
+        panic!("body panicked");
+    });
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.state(), IterState::Panicked);
+}
+
+#[test]
+fn debug() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        assert!(format!("{y:?}").starts_with("LocalIterContext {"));
+        y.ret(1).await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    assert!(format!("{iter:?}").starts_with("LocalIter { state: Suspended"));
+}
+
+#[test]
+fn buffered() {
+    let count = Cell::new(0);
+    let count = &count;
+    let mut iter = LocalIter::new_buffered(2, |mut y| async move {
+        for i in 0..3 {
+            count.set(count.get() + 1);
+            y.ret(i).await;
+        }
+    });
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(count.get(), 2);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(count.get(), 2);
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(count.get(), 3);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn fill() {
+    let mut iter = LocalIter::new_buffered(4, |mut y| async move {
+        y.ret_iter(0..6).await;
+    });
+    let mut buf = [const { MaybeUninit::uninit() }; 5];
+    assert_eq!(iter.fill(&mut buf), 5);
+    assert_eq!(buf.map(|v| unsafe { v.assume_init() }), [0, 1, 2, 3, 4]);
+    assert_eq!(iter.next_chunk::<2>(), Err(vec![5]));
+}
+
+#[test]
+fn fold() {
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret_iter(["a", "b"]).await;
+    });
+    assert_eq!(iter.fold(String::new(), |acc, v| acc + v), "ab");
+}
+
+#[test]
+fn delegate_iter() {
+    let count = Cell::new(0);
+    let count = &count;
+    let mut iter = LocalIter::new(|mut y| async move {
+        count.set(count.get() + 1);
+        y.ret(0).await;
+        count.set(count.get() + 1);
+        y.delegate_iter(1..1000).await;
+        count.set(count.get() + 1);
+        y.ret(1000).await;
+    });
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(count.get(), 2);
+    assert_eq!(iter.size_hint(), (998, None));
+    assert_eq!(iter.by_ref().take(998).count(), 998);
+    assert_eq!(count.get(), 2);
+    assert_eq!(iter.next(), Some(1000));
+    assert_eq!(count.get(), 3);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn delegate_iter_panic() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        y.delegate_iter((0..3).map(|v| if v < 1 { v } else { panic!("error") }))
+            .await;
+    });
+    assert_eq!(iter.try_next().ok(), Some(Some(0)));
+    assert!(iter.try_next().is_err());
+    assert_eq!(iter.state(), IterState::Panicked);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn from_iter_size_hint() {
+    let mut iter = LocalIter::from_iter(0..5);
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.state(), IterState::NotStarted);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.state(), IterState::Suspended);
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(iter.state(), IterState::Finished);
+}
+
+struct Node {
+    value: u32,
+    children: Vec<Node>,
+}
+
+async fn walk<'a>(mut y: LocalIterContext<'a, u32>, node: &'a Node) {
+    y.ret(node.value).await;
+    for child in &node.children {
+        y.ret_nested(|y| walk(y, child)).await;
+    }
+}
+
+#[test]
+fn ret_nested() {
+    let leaf = |value| Node {
+        value,
+        children: Vec::new(),
+    };
+    let tree = Node {
+        value: 1,
+        children: vec![
+            Node {
+                value: 2,
+                children: vec![leaf(3), leaf(4)],
+            },
+            leaf(5),
+        ],
+    };
+    let iter = LocalIter::new(|y| walk(y, &tree));
+    assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn ret_nested_drop_order() {
+    struct Guard<'a>(&'a RefCell<Vec<&'static str>>, &'static str);
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.0.borrow_mut().push(self.1);
+        }
+    }
+    let dropped = RefCell::new(Vec::new());
+    let dropped = &dropped;
+    let mut iter = LocalIter::new(|mut y| async move {
+        let _guard = Guard(dropped, "outer");
+        y.ret_nested(|mut y| async move {
+            let _guard = Guard(dropped, "inner");
+            y.ret(1).await;
+            y.ret(2).await;
+        })
+        .await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    drop(iter);
+    assert_eq!(*dropped.borrow(), ["inner", "outer"]);
+}
+
+#[test]
+fn ret_nested_deep() {
+    async fn count_down(mut y: LocalIterContext<'_, u32>, n: u32) {
+        if n > 0 {
+            y.ret(n).await;
+            y.ret_nested(move |y| count_down(y, n - 1)).await;
+        }
+    }
+    let iter = LocalIter::new(|y| count_down(y, 100_000));
+    assert_eq!(iter.count(), 100_000);
+}
+
+#[test]
+fn ret_nested_drop() {
+    struct Guard(Rc<Cell<usize>>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+    let dropped = Rc::new(Cell::new(0));
+    let guard = Guard(dropped.clone());
+    let mut iter = LocalIter::new_buffered(2, |mut y| async move {
+        y.ret(1).await;
+        y.ret_nested(move |mut y| async move {
+            let _guard = guard;
+            y.ret(2).await;
+            y.ret(3).await;
+        })
+        .await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    drop(iter);
+    assert_eq!(dropped.get(), 1);
+}
+
+#[test]
+fn ret_with() {
+    let computed = Cell::new(0);
+    let computed = &computed;
+    let mut iter = LocalIter::new(|mut y| async move {
+        for i in 0..10 {
+            y.ret_with(|| {
+                computed.set(computed.get() + 1);
+                i
+            })
+            .await;
+        }
+    });
+    assert_eq!(iter.nth(5), Some(5));
+    assert_eq!(computed.get(), 1);
+    assert_eq!(iter.by_ref().skip(2).collect::<Vec<_>>(), vec![8, 9]);
+    assert_eq!(computed.get(), 3);
+}
@@ -0,0 +1,166 @@
+use std::{
+    cell::{Cell, OnceCell},
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::channel,
+    },
+    thread,
+};
+
+use yield_return::{Iter, LocalIter, LocalMemo, Memo};
+
+#[test]
+fn memo() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let memo = Iter::new({
+        let count = count.clone();
+        |mut y| async move {
+            for i in 0..5 {
+                count.fetch_add(1, Ordering::SeqCst);
+                y.ret(i).await;
+            }
+        }
+    })
+    .memo();
+    let mut a = memo.iter();
+    assert_eq!(a.next(), Some(0));
+    assert_eq!(a.next(), Some(1));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    let b: Vec<_> = memo.iter().take(2).collect();
+    assert_eq!(b, vec![0, 1]);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert_eq!((&memo).into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(a.collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(count.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn memo_threads() {
+    let memo = Iter::new(|mut y| async move {
+        y.ret_iter(0..100).await;
+    })
+    .memo();
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| assert_eq!(memo.iter().sum::<u32>(), 4950));
+        }
+    });
+}
+
+#[test]
+fn memo_cached_read_while_pulling() {
+    let (started_tx, started_rx) = channel();
+    let (resume_tx, resume_rx) = channel();
+    let memo = Memo::new((0..3).inspect(move |&i| {
+        if i == 1 {
+            started_tx.send(()).unwrap();
+            resume_rx.recv().unwrap();
+        }
+    }));
+    let mut a = memo.iter();
+    assert_eq!(a.next(), Some(0));
+    thread::scope(|s| {
+        let b = s.spawn(|| memo.iter().collect::<Vec<_>>());
+        started_rx.recv().unwrap();
+        assert_eq!(memo.iter().next(), Some(0));
+        assert_eq!(memo.iter().size_hint(), (1, None));
+        resume_tx.send(()).unwrap();
+        assert_eq!(b.join().unwrap(), vec![0, 1, 2]);
+    });
+    assert_eq!(a.collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn memo_read_from_source() {
+    let memo: &'static OnceLock<Memo<Iter<u32>>> = Box::leak(Box::new(OnceLock::new()));
+    let iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        let cached: Vec<_> = memo.get().unwrap().iter().take(1).collect();
+        y.ret(cached[0] + 1).await;
+    });
+    let memo = memo.get_or_init(|| iter.memo());
+    assert_eq!(memo.iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn memo_size_hint() {
+    let memo = Memo::new(0..3);
+    let mut a = memo.iter();
+    assert_eq!(a.size_hint(), (3, Some(3)));
+    a.next();
+    assert_eq!(a.size_hint(), (2, Some(2)));
+    assert_eq!(memo.iter().size_hint(), (3, Some(3)));
+}
+
+#[test]
+fn memo_panic() {
+    let memo = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        panic!("error");
+    })
+    .memo();
+    let mut a = memo.iter();
+    assert_eq!(a.next(), Some(1));
+    assert!(catch_unwind(AssertUnwindSafe(|| a.next())).is_err());
+    let mut b = memo.iter();
+    assert_eq!(b.next(), Some(1));
+    assert!(catch_unwind(AssertUnwindSafe(|| b.next())).is_err());
+    assert_eq!(memo.iter().size_hint(), (1, Some(1)));
+}
+
+#[test]
+fn local_memo() {
+    let count = Cell::new(0);
+    let count = &count;
+    let memo = LocalIter::new(|mut y| async move {
+        for i in 0..3 {
+            count.set(count.get() + 1);
+            y.ret(i).await;
+        }
+    })
+    .memo();
+    let a = memo.iter();
+    let mut b = a.clone();
+    assert_eq!(b.next(), Some(0));
+    assert_eq!(count.get(), 1);
+    assert_eq!(a.collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(b.collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn local_memo_new() {
+    let memo = LocalMemo::new(vec!["a", "b"]);
+    assert_eq!(memo.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(memo.iter().count(), 2);
+}
+
+#[test]
+fn local_memo_panic() {
+    let memo = LocalMemo::new((0..3).map(|i| if i == 1 { panic!("error") } else { i }));
+    let mut a = memo.iter();
+    assert_eq!(a.next(), Some(0));
+    assert!(catch_unwind(AssertUnwindSafe(|| a.next())).is_err());
+    let mut b = memo.iter();
+    assert_eq!(b.next(), Some(0));
+    assert!(catch_unwind(AssertUnwindSafe(|| b.next())).is_err());
+}
+
+#[test]
+fn local_memo_read_from_source() {
+    let memo: &'static OnceCell<LocalMemo<LocalIter<u32>>> = Box::leak(Box::new(OnceCell::new()));
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+        let cached: Vec<_> = memo.get().unwrap().iter().take(1).collect();
+        y.ret(cached[0] + 1).await;
+        memo.get().unwrap().iter().count();
+    });
+    let memo = memo.get_or_init(|| iter.memo());
+    let mut a = memo.iter();
+    assert_eq!(a.next(), Some(1));
+    assert_eq!(a.next(), Some(2));
+    assert!(catch_unwind(AssertUnwindSafe(|| a.next())).is_err());
+    assert_eq!(memo.iter().take(2).collect::<Vec<_>>(), vec![1, 2]);
+}
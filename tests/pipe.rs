@@ -0,0 +1,111 @@
+use std::{future::pending, time::Duration};
+
+use futures::{StreamExt, stream};
+use rt_local::runtime::core::test;
+use utils::sleep;
+use yield_return::Pipe;
+
+mod utils;
+
+fn dedup<'a>() -> Pipe<'a, i32, i32> {
+    Pipe::new(|mut input, mut y| async move {
+        let mut last = None;
+        while let Some(value) = input.next().await {
+            if last != Some(value) {
+                y.ret(value).await;
+                last = Some(value);
+            }
+        }
+    })
+}
+
+#[test]
+fn no_value() {
+    let list: Vec<_> = dedup().apply([]).collect();
+    assert_eq!(list, vec![]);
+}
+
+#[test]
+fn single_stage() {
+    let list: Vec<_> = dedup().apply([1, 1, 2, 3, 3, 1]).collect();
+    assert_eq!(list, vec![1, 2, 3, 1]);
+}
+
+#[test]
+fn then() {
+    let pipe = dedup().then(|mut input, mut y| async move {
+        let mut group = Vec::new();
+        while let Some(value) = input.next().await {
+            group.push(value);
+            if group.len() == 2 {
+                y.ret(std::mem::take(&mut group)).await;
+            }
+        }
+        if !group.is_empty() {
+            y.ret(group).await;
+        }
+    });
+    let list: Vec<_> = pipe.apply([1, 1, 2, 3, 3, 4, 5]).collect();
+    assert_eq!(list, vec![vec![1, 2], vec![3, 4], vec![5]]);
+}
+
+#[test]
+fn then_pipe() {
+    let list: Vec<_> = dedup().then_pipe(dedup()).apply([1, 1, 2, 2]).collect();
+    assert_eq!(list, vec![1, 2]);
+}
+
+#[test]
+fn lazy() {
+    let mut iter = dedup().apply((0..).map(|x| x / 2));
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), Some(2));
+}
+
+#[test]
+fn values_with_lifetime() {
+    let items = vec![1, 2];
+    let pipe = Pipe::new(|mut input, mut y| async move {
+        while let Some(value) = input.next().await {
+            y.ret(value).await;
+        }
+    });
+    let list: Vec<&i32> = pipe.apply(&items).collect();
+    assert_eq!(list, vec![&1, &2]);
+}
+
+#[test]
+#[should_panic]
+fn apply_use_pending() {
+    let pipe = Pipe::<i32, i32>::new(|_input, mut y| async move {
+        y.ret(1).await;
+        pending::<()>().await;
+    });
+    let _: Vec<_> = pipe.apply([1]).collect();
+}
+
+#[test]
+async fn apply_stream() {
+    let list: Vec<_> = dedup()
+        .apply_stream(stream::iter([1, 1, 2]))
+        .collect()
+        .await;
+    assert_eq!(list, vec![1, 2]);
+}
+
+#[test]
+async fn apply_stream_use_sleep() {
+    let source = stream::iter([1, 1, 2]).then(|x| async move {
+        sleep(Duration::from_millis(10)).await;
+        x
+    });
+    let pipe = dedup().then(|mut input, mut y| async move {
+        while let Some(value) = input.next().await {
+            sleep(Duration::from_millis(10)).await;
+            y.ret(value).await;
+        }
+    });
+    let list: Vec<_> = pipe.apply_stream(source).collect().await;
+    assert_eq!(list, vec![1, 2]);
+}
@@ -0,0 +1,102 @@
+use std::{sync::Mutex, thread};
+
+use yield_return::Pipeline;
+
+#[test]
+fn no_value() {
+    let list: Vec<u32> = Pipeline::new(1, |mut _y| async move {}).run(|iter| iter.collect());
+    assert_eq!(list, vec![]);
+}
+
+#[test]
+fn values() {
+    let list: Vec<_> = Pipeline::new(1, |mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    })
+    .run(|iter| iter.collect());
+    assert_eq!(list, vec![1, 2]);
+}
+
+#[test]
+fn stages() {
+    let list: Vec<_> = Pipeline::from_iter(2, ["1", "2", "3"])
+        .stage(2, |upstream, mut y| async move {
+            for s in upstream {
+                y.ret(s.parse::<i32>().unwrap()).await;
+            }
+        })
+        .stage(0, |upstream, mut y| async move {
+            for value in upstream {
+                y.ret(value).await;
+                y.ret(value * 10).await;
+            }
+        })
+        .run(|iter| iter.collect());
+    assert_eq!(list, vec![1, 10, 2, 20, 3, 30]);
+}
+
+#[test]
+fn stages_on_other_threads() {
+    let ids = Mutex::new(Vec::new());
+    let ids = &ids;
+    Pipeline::new(1, |mut y| async move {
+        ids.lock().unwrap().push(thread::current().id());
+        y.ret(1).await;
+    })
+    .stage(1, |upstream, mut y| async move {
+        ids.lock().unwrap().push(thread::current().id());
+        y.ret_iter(upstream).await;
+    })
+    .run(|iter| iter.count());
+    let ids = ids.lock().unwrap();
+    assert_eq!(ids.len(), 2);
+    assert_ne!(ids[0], ids[1]);
+    assert!(!ids.contains(&thread::current().id()));
+}
+
+#[test]
+fn values_with_lifetime() {
+    let items = vec![1, 2];
+    let items = &items;
+    let list: Vec<&i32> = Pipeline::new(1, |mut y| async move {
+        y.ret(&items[0]).await;
+        y.ret(&items[1]).await;
+    })
+    .run(|iter| iter.collect());
+    assert_eq!(list, vec![&1, &2]);
+}
+
+#[test]
+fn consumer_stops_early() {
+    let first = Pipeline::new(0, |mut y| async move {
+        let mut i = 0;
+        loop {
+            y.ret(i).await;
+            i += 1;
+        }
+    })
+    .stage(0, |upstream, mut y| async move {
+        y.ret_iter(upstream).await;
+    })
+    .run(|mut iter| iter.next());
+    assert_eq!(first, Some(0));
+}
+
+#[test]
+#[should_panic(expected = "stage panicked")]
+fn panic_in_stage() {
+    let _: Vec<i32> = Pipeline::from_iter(1, [1, 2, 3])
+        .stage(1, |upstream, mut y| async move {
+            for value in upstream {
+                if value == 2 {
+                    panic!("stage panicked");
+                }
+                y.ret(value).await;
+            }
+        })
+        .stage(1, |upstream, mut y| async move {
+            y.ret_iter(upstream).await;
+        })
+        .run(|iter| iter.collect());
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "serde")]
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeSeed;
+
+use yield_return::{Iter, IterMap, IterSeq, LocalIter};
+
+#[test]
+fn seq() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret("a").await;
+        y.ret("b").await;
+    });
+    let json = serde_json::to_string(&iter.into_seq()).unwrap();
+    assert_eq!(json, r#"["a","b"]"#);
+}
+
+#[test]
+fn seq_empty() {
+    let iter = LocalIter::<i32>::new(|_| async {});
+    let json = serde_json::to_string(&iter.into_seq()).unwrap();
+    assert_eq!(json, "[]");
+}
+
+#[test]
+fn seq_nested() {
+    let iter = LocalIter::new(|mut y| async move {
+        for i in 0..2 {
+            y.ret(IterSeq::new(0..i)).await;
+        }
+    });
+    let json = serde_json::to_string(&iter.into_seq()).unwrap();
+    assert_eq!(json, "[[],[0]]");
+}
+
+#[test]
+fn seq_twice() {
+    let seq = IterSeq::new([1, 2]);
+    assert_eq!(serde_json::to_string(&seq).unwrap(), "[1,2]");
+    assert!(serde_json::to_string(&seq).is_err());
+}
+
+#[test]
+fn map() {
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret(("x", 1)).await;
+        y.ret(("y", 2)).await;
+    });
+    let value = serde_json::to_value(iter.into_map()).unwrap();
+    let map: BTreeMap<String, i32> = serde_json::from_value(value).unwrap();
+    assert_eq!(map, BTreeMap::from([("x".into(), 1), ("y".into(), 2)]));
+}
+
+#[test]
+fn map_twice() {
+    let map = IterMap::new([("a", 1)]);
+    assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":1}"#);
+    assert!(serde_json::to_string(&map).is_err());
+}
+
+#[test]
+fn deserialize_seq() {
+    let value = serde_json::json!(["a", "b"]);
+    let list: Vec<String> = Iter::deserialize_seq(value)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(list, vec!["a", "b"]);
+}
+
+#[test]
+fn deserialize_seq_not_seq() {
+    let value = serde_json::json!({ "a": 1 });
+    let list: Vec<Result<i32, _>> = LocalIter::deserialize_seq(value).collect();
+    assert_eq!(list.len(), 1);
+    assert!(list[0].is_err());
+}
+
+#[test]
+fn deserialize_seq_with() {
+    let json = b"[1, 2, 3] ";
+    let iter = LocalIter::<i32>::deserialize_seq_with(move |seed| {
+        let mut de = serde_json::Deserializer::from_reader(&json[..]);
+        seed.deserialize(&mut de)?;
+        de.end()
+    });
+    let list: Vec<_> = iter.collect::<Result<_, _>>().unwrap();
+    assert_eq!(list, vec![1, 2, 3]);
+}
+
+#[test]
+fn deserialize_seq_with_invalid_element() {
+    let json = br#"[1, "x", 3]"#;
+    let iter = Iter::<i32>::deserialize_seq_with(move |seed| {
+        seed.deserialize(&mut serde_json::Deserializer::from_reader(&json[..]))
+    });
+    let list: Vec<_> = iter.collect();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].as_ref().unwrap(), &1);
+    assert!(list[1].is_err());
+}
+
+#[test]
+fn deserialize_seq_drop() {
+    let json = "[0".to_string() + &",0".repeat(10000) + "]";
+    let mut iter = Iter::<i32>::deserialize_seq_with(move |seed| {
+        seed.deserialize(&mut serde_json::Deserializer::from_str(&json))
+    });
+    assert_eq!(iter.next().unwrap().unwrap(), 0);
+    drop(iter);
+}
+
+#[test]
+#[should_panic(expected = "deserializer panicked")]
+fn deserialize_seq_panic() {
+    let iter = Iter::<i32>::deserialize_seq_with(|_| -> Result<(), serde_json::Error> {
+        panic!("deserializer panicked")
+    });
+    let _: Vec<_> = iter.collect();
+}
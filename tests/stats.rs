@@ -0,0 +1,61 @@
+#![cfg(feature = "stats")]
+
+use std::{thread::sleep, time::Duration};
+
+use futures::{StreamExt, executor::block_on};
+use yield_return::{AsyncIter, Iter, LocalAsyncIter, LocalIter};
+
+#[test]
+fn counts() {
+    let mut iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_eq!(iter.stats().items, 0);
+    assert_eq!(iter.stats().polls, 0);
+    let _: Vec<_> = iter.by_ref().collect();
+    let stats = iter.stats();
+    assert_eq!(stats.items, 2);
+    assert_eq!(stats.polls, 3);
+    assert_eq!(stats.pending_polls, 0);
+}
+
+#[test]
+fn pending_polls() {
+    let mut iter = AsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+        y.yield_now().await;
+        y.ret(2).await;
+    });
+    block_on(async { while iter.next().await.is_some() {} });
+    let stats = iter.stats();
+    assert_eq!(stats.items, 2);
+    assert_eq!(stats.polls, 4);
+    assert_eq!(stats.pending_polls, 1);
+}
+
+#[test]
+fn local_pending_polls() {
+    let mut iter = LocalAsyncIter::new(|mut y| async move {
+        y.yield_now().await;
+        y.ret(1).await;
+    });
+    block_on(async { while iter.next().await.is_some() {} });
+    let stats = iter.stats();
+    assert_eq!(stats.items, 1);
+    assert_eq!(stats.pending_polls, 1);
+}
+
+#[test]
+fn times() {
+    let mut iter = LocalIter::new(|mut y| async move {
+        sleep(Duration::from_millis(20));
+        y.ret(1).await;
+    });
+    assert_eq!(iter.next(), Some(1));
+    sleep(Duration::from_millis(20));
+    assert_eq!(iter.next(), None);
+    let stats = iter.stats();
+    assert!(stats.body_time >= Duration::from_millis(20));
+    assert!(stats.consumer_time >= Duration::from_millis(20));
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "testing")]
+
+use yield_return::{
+    Iter, LocalAsyncIter, LocalIter,
+    testing::{Step, Stepper, assert_yields, check_ret_awaited, mock_future},
+};
+
+#[test]
+fn stepper_with_mock_future() {
+    let (fut, handle) = mock_future();
+    let iter = LocalAsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(fut.await).await;
+    });
+    let mut s = Stepper::new(iter);
+    assert_eq!(s.step(), Step::Yield(1));
+    assert!(!handle.is_waiting());
+    assert_eq!(s.step(), Step::Pending);
+    assert!(handle.is_waiting());
+    assert_eq!(s.wake_count(), 0);
+    assert_eq!(s.step(), Step::Pending);
+    handle.complete(2);
+    assert_eq!(s.wake_count(), 1);
+    assert!(!handle.is_waiting());
+    s.assert_steps([Step::Yield(2), Step::Done, Step::Done]);
+    assert_eq!(s.steps(), 6);
+}
+
+#[test]
+#[should_panic(expected = "step 1")]
+fn assert_steps_mismatch() {
+    let iter = LocalAsyncIter::new(|mut y| async move {
+        y.ret(1).await;
+    });
+    Stepper::new(iter).assert_steps([Step::Yield(1), Step::Yield(2)]);
+}
+
+#[test]
+fn assert_yields_ok() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_yields(iter, [1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "extra values")]
+fn assert_yields_extra() {
+    let iter = Iter::new(|mut y| async move {
+        y.ret(1).await;
+        y.ret(2).await;
+    });
+    assert_yields(iter, [1]);
+}
+
+#[test]
+fn check_ret_awaited_ok() {
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret(1).await;
+    });
+    assert_eq!(check_ret_awaited(iter), Ok(vec![1]));
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn check_ret_awaited_err() {
+    let iter = LocalIter::new(|mut y| async move {
+        y.ret(1);
+    });
+    let message = check_ret_awaited(iter).unwrap_err();
+    assert!(message.contains("The result of `ret` is not await."));
+
+    let iter = Iter::new(|mut y| async move {
+        y.ret(1);
+        y.ret(2);
+    });
+    assert!(check_ret_awaited(iter).is_err());
+}
+
+#[test]
+#[should_panic(expected = "body panicked")]
+fn check_ret_awaited_other_panic() {
+    let iter = LocalIter::<u32>::new(|_| async move {
+        panic!("body panicked");
+    });
+    let _ = check_ret_awaited(iter);
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    fmt::{Debug, Write},
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use tracing::{
+    Event, Id, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Record},
+};
+use yield_return::{Iter, LocalIter};
+
+#[derive(Default)]
+struct Recorder {
+    next_id: AtomicU64,
+    stack: Mutex<Vec<u64>>,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        write!(self.0, " {field}={value:?}").unwrap();
+    }
+    fn record_str(&mut self, field: &Field, value: &str) {
+        write!(self.0, " {field}={value}").unwrap();
+    }
+}
+
+impl Recorder {
+    fn push(&self, s: String) {
+        self.log.lock().unwrap().push(s);
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut fields = Fields(String::new());
+        span.record(&mut fields);
+        self.push(format!("new {}{}", span.metadata().name(), fields.0));
+        Id::from_u64(id)
+    }
+    fn record(&self, _span: &Id, values: &Record<'_>) {
+        let mut fields = Fields(String::new());
+        values.record(&mut fields);
+        self.push(format!("record{}", fields.0));
+    }
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        let in_span = !self.stack.lock().unwrap().is_empty();
+        self.push(format!("event{} in_span={in_span}", fields.0));
+    }
+    fn enter(&self, span: &Id) {
+        self.stack.lock().unwrap().push(span.into_u64());
+    }
+    fn exit(&self, _span: &Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+fn record(f: impl FnOnce()) -> Vec<String> {
+    let recorder = Recorder::default();
+    let log = recorder.log.clone();
+    tracing::subscriber::with_default(recorder, f);
+    log.lock().unwrap().clone()
+}
+
+#[test]
+fn yield_and_complete() {
+    let line = line!() + 2;
+    let log = record(|| {
+        let iter = Iter::new(|mut y| async move {
+            y.ret(1).await;
+            y.ret(2).await;
+        })
+        .with_name("numbers");
+        let _: Vec<_> = iter.collect();
+    });
+    assert_eq!(
+        log,
+        vec![
+            format!("new yield_return location=tests/tracing.rs:{line}:20"),
+            "record name=numbers".to_string(),
+            "event message=yield in_span=true".to_string(),
+            "event message=yield in_span=true".to_string(),
+            "event message=complete in_span=true".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn drop_before_completion() {
+    let log = record(|| {
+        let mut iter = LocalIter::new(|mut y| async move {
+            y.ret(1).await;
+            y.ret(2).await;
+        });
+        iter.next();
+    });
+    assert_eq!(
+        log[1..],
+        [
+            "event message=yield in_span=true",
+            "event message=drop before completion in_span=true"
+        ]
+    );
+}
+
+#[test]
+fn panic() {
+    let log = record(|| {
+        let mut iter = Iter::<u32>::new(|_| async move {
+            panic!("body panicked");
+        });
+        catch_unwind(AssertUnwindSafe(|| iter.next())).unwrap_err();
+    });
+    assert_eq!(
+        log[1..],
+        ["event message=panic message=body panicked in_span=true"]
+    );
+}
@@ -1,7 +1,13 @@
+#![allow(dead_code)]
+
 use std::{
     future::Future,
     pin::Pin,
-    task::{Context, Poll},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
     time::{Duration, Instant},
 };
 
@@ -29,3 +35,18 @@ impl Future for Sleep {
         }
     }
 }
+
+pub fn counting_waker() -> (Waker, Arc<AtomicUsize>) {
+    struct CountingWaker(Arc<AtomicUsize>);
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    let count = Arc::new(AtomicUsize::new(0));
+    let waker = Waker::from(Arc::new(CountingWaker(count.clone())));
+    (waker, count)
+}